@@ -0,0 +1,47 @@
+use std::hint::black_box;
+
+use common::Solution;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+/// Benchmark parsing and both parts of `S`, separately, on the sample and (if
+/// it's available) the real input.
+fn bench<S: Solution>(c: &mut Criterion) {
+    let mut group = c.benchmark_group(format!("day{:02}", S::DAY));
+
+    let mut inputs = vec![("sample", S::SAMPLE.to_owned())];
+    match common::input::load(S::DAY, None) {
+        Ok(input) => inputs.push(("input", input)),
+        Err(e) => eprintln!("skipping real input: {e}"),
+    }
+
+    for (name, input) in &inputs {
+        group.bench_with_input(BenchmarkId::new("parse", name), input, |b, input| {
+            b.iter(|| S::parse(black_box(input)).unwrap())
+        });
+
+        let parsed = S::parse(input).unwrap();
+        group.bench_with_input(BenchmarkId::new("part1", name), &parsed, |b, parsed| {
+            b.iter(|| S::part1(black_box(parsed)))
+        });
+        group.bench_with_input(BenchmarkId::new("part2", name), &parsed, |b, parsed| {
+            b.iter(|| S::part2(black_box(parsed)))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench::<day01::Day01>,
+    bench::<day02::Day02>,
+    bench::<day03::Day03>,
+    bench::<day04::Day04>,
+    bench::<day05::Day05>,
+    bench::<day06::Day06>,
+    bench::<day07::Day07>,
+    bench::<day09::Day09>,
+    bench::<day10::Day10>,
+    bench::<day13::Day13>,
+);
+criterion_main!(benches);
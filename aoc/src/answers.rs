@@ -0,0 +1,102 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+pub const DEFAULT_PATH: &str = "answers.json";
+
+/// Known answers by day, input hash and part.
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Answers(BTreeMap<u8, BTreeMap<String, BTreeMap<u8, String>>>);
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Fail { expected: String },
+    Unknown,
+}
+
+impl Answers {
+    /// Load answers from `path`, starting out empty if it doesn't exist yet.
+    pub fn load(path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        match std::fs::read_to_string(path) {
+            Ok(s) => Ok(serde_json::from_str(&s).map_err(|e| format!("{}: {e}", path.display()))?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(format!("failed to read {}: {e}", path.display()).into()),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        let mut json = serde_json::to_string_pretty(self)?;
+        json.push('\n');
+        std::fs::write(path, json)
+            .map_err(|e| format!("failed to write {}: {e}", path.display()))?;
+        Ok(())
+    }
+
+    pub fn get(&self, day: u8, hash: &str, part: u8) -> Option<&str> {
+        self.0.get(&day)?.get(hash)?.get(&part).map(String::as_str)
+    }
+
+    /// Record an answer unless one is already known. Returns whether it was added.
+    pub fn insert(&mut self, day: u8, hash: &str, part: u8, answer: &str) -> bool {
+        let parts = self
+            .0
+            .entry(day)
+            .or_default()
+            .entry(hash.to_owned())
+            .or_default();
+        if parts.contains_key(&part) {
+            return false;
+        }
+        parts.insert(part, answer.to_owned());
+        true
+    }
+
+    pub fn check(&self, day: u8, hash: &str, part: u8, answer: &str) -> Status {
+        match self.get(day, hash, part) {
+            Some(expected) if expected == answer => Status::Pass,
+            Some(expected) => Status::Fail {
+                expected: expected.to_owned(),
+            },
+            None => Status::Unknown,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Answers, Status};
+
+    #[test]
+    fn test_check() {
+        let mut answers = Answers::default();
+        assert!(answers.insert(1, "abc", 1, "24000"));
+        assert!(!answers.insert(1, "abc", 1, "12"));
+
+        assert_eq!(answers.check(1, "abc", 1, "24000"), Status::Pass);
+        assert_eq!(
+            answers.check(1, "abc", 1, "12"),
+            Status::Fail {
+                expected: "24000".to_owned()
+            }
+        );
+        assert_eq!(answers.check(1, "abc", 2, "45000"), Status::Unknown);
+        assert_eq!(answers.check(1, "def", 1, "24000"), Status::Unknown);
+    }
+
+    #[test]
+    fn test_serde() {
+        let mut answers = Answers::default();
+        answers.insert(10, "abc", 2, "#.\n.#\n");
+        answers.insert(1, "abc", 1, "24000");
+
+        let json = serde_json::to_string(&answers).unwrap();
+        assert_eq!(
+            json,
+            r##"{"1":{"abc":{"1":"24000"}},"10":{"abc":{"2":"#.\n.#\n"}}}"##
+        );
+        assert_eq!(serde_json::from_str::<Answers>(&json).unwrap(), answers);
+    }
+}
@@ -0,0 +1,83 @@
+use std::fmt::Write;
+use std::time::Duration;
+
+use common::ParseError;
+
+use crate::registry::Day;
+
+/// Median time of each step over a number of runs.
+#[derive(Clone, Copy, Debug)]
+pub struct Timings {
+    pub parse: Duration,
+    pub part1: Duration,
+    pub part2: Duration,
+}
+
+fn median(mut v: Vec<Duration>) -> Duration {
+    v.sort_unstable();
+    v[v.len() / 2]
+}
+
+pub fn measure(day: &Day, input: &str, iterations: usize) -> Result<Timings, ParseError> {
+    let mut parse = vec![];
+    let mut part1 = vec![];
+    let mut part2 = vec![];
+
+    for _ in 0..iterations.max(1) {
+        let report = (day.solve)(input, &[1, 2])?;
+        parse.push(report.parse);
+        part1.push(report.answers[0].elapsed);
+        part2.push(report.answers[1].elapsed);
+    }
+
+    Ok(Timings {
+        parse: median(parse),
+        part1: median(part1),
+        part2: median(part2),
+    })
+}
+
+/// Render a Markdown table with one row per `(day, input name, timings)`.
+pub fn table(rows: &[(u8, &str, Timings)]) -> String {
+    let mut output = String::new();
+    output.push_str("| Day | Input | Parse | Part 1 | Part 2 |\n");
+    output.push_str("|----:|:------|------:|-------:|-------:|\n");
+    for (day, input, t) in rows {
+        writeln!(
+            output,
+            "| {day:02} | {input} | {:.1?} | {:.1?} | {:.1?} |",
+            t.parse, t.part1, t.part2
+        )
+        .unwrap();
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{median, table, Timings};
+
+    #[test]
+    fn test_median() {
+        let ms = Duration::from_millis;
+        assert_eq!(median(vec![ms(3), ms(1), ms(2)]), ms(2));
+        assert_eq!(median(vec![ms(5)]), ms(5));
+    }
+
+    #[test]
+    fn test_table() {
+        let t = Timings {
+            parse: Duration::from_micros(12),
+            part1: Duration::from_nanos(340),
+            part2: Duration::from_millis(5),
+        };
+        assert_eq!(
+            table(&[(1, "sample", t)]),
+            "| Day | Input | Parse | Part 1 | Part 2 |\n\
+             |----:|:------|------:|-------:|-------:|\n\
+             | 01 | sample | 12.0µs | 340.0ns | 5.0ms |\n"
+        );
+    }
+}
@@ -0,0 +1,273 @@
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com/2022";
+
+const USER_AGENT: &str = "github.com/lithammer/advent_of_code_2022";
+
+#[derive(Debug)]
+pub enum Error {
+    /// The server didn't accept the session cookie.
+    NotLoggedIn,
+    /// The puzzle doesn't exist or hasn't unlocked yet.
+    NotFound {
+        day: u8,
+    },
+    /// The server answered with something that doesn't look like an input.
+    Unexpected {
+        status: u16,
+        body: String,
+    },
+    Http(Box<ureq::Error>),
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::NotLoggedIn => write!(f, "not logged in, check the session cookie"),
+            Error::NotFound { day } => write!(f, "no input for day {day}, has it unlocked yet?"),
+            Error::Unexpected { status, body } => {
+                let line = body.lines().next().unwrap_or_default();
+                write!(f, "unexpected response (HTTP {status}): {line}")
+            }
+            Error::Http(e) => e.fmt(f),
+            Error::Io { path, source } => write!(f, "failed to write {}: {source}", path.display()),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Fetched {
+    Cached,
+    Downloaded,
+}
+
+pub struct Fetcher {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+    min_interval: Duration,
+    last_request: Option<Instant>,
+}
+
+impl Fetcher {
+    pub fn new(base_url: &str, session: &str, min_interval: Duration) -> Self {
+        Self {
+            agent: ureq::AgentBuilder::new().user_agent(USER_AGENT).build(),
+            base_url: base_url.trim_end_matches('/').to_owned(),
+            session: session.trim().to_owned(),
+            min_interval,
+            last_request: None,
+        }
+    }
+
+    /// Download the input for `day`, waiting if the previous request was made
+    /// less than `min_interval` ago.
+    pub fn fetch(&mut self, day: u8) -> Result<String, Error> {
+        if let Some(last) = self.last_request {
+            std::thread::sleep(self.min_interval.saturating_sub(last.elapsed()));
+        }
+        self.last_request = Some(Instant::now());
+
+        let url = format!("{}/day/{day}/input", self.base_url);
+        let result = self
+            .agent
+            .get(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .call();
+
+        let (status, body) = match result {
+            Ok(response) => (response.status(), read_body(response)?),
+            Err(ureq::Error::Status(status, response)) => (status, read_body(response)?),
+            Err(e) => return Err(Error::Http(Box::new(e))),
+        };
+
+        match status {
+            _ if body.contains("Please log in") => Err(Error::NotLoggedIn),
+            200 if body.trim_start().starts_with('<') => Err(Error::Unexpected { status, body }),
+            200 => Ok(body),
+            404 => Err(Error::NotFound { day }),
+            _ => Err(Error::Unexpected { status, body }),
+        }
+    }
+
+    /// Download the input for `day` into `path`, unless it's already there. An
+    /// empty file is a placeholder (see `aoc new`) and gets replaced.
+    pub fn fetch_cached(&mut self, day: u8, path: &Path, force: bool) -> Result<Fetched, Error> {
+        if !force && common::input::is_present(path) {
+            return Ok(Fetched::Cached);
+        }
+
+        let input = self.fetch(day)?;
+        let io_error = |source| Error::Io {
+            path: path.to_owned(),
+            source,
+        };
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(io_error)?;
+        }
+        std::fs::write(path, input).map_err(io_error)?;
+
+        Ok(Fetched::Downloaded)
+    }
+}
+
+fn read_body(response: ureq::Response) -> Result<String, Error> {
+    response
+        .into_string()
+        .map_err(|e| Error::Http(Box::new(e.into())))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc;
+    use std::time::{Duration, Instant};
+
+    use super::{Error, Fetched, Fetcher};
+
+    /// Serve one canned `(status, body)` response per connection and report
+    /// each request line and cookie back over the returned channel.
+    fn serve(responses: Vec<(u16, &'static str)>) -> (String, mpsc::Receiver<(String, String)>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}/2022", listener.local_addr().unwrap());
+        let (tx, rx) = mpsc::channel();
+
+        std::thread::spawn(move || {
+            for (status, body) in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request = String::new();
+                reader.read_line(&mut request).unwrap();
+                let mut cookie = String::new();
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some(value) = line.strip_prefix("Cookie: ") {
+                        cookie = value.trim().to_owned();
+                    }
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                }
+                tx.send((request.trim().to_owned(), cookie)).unwrap();
+                write!(
+                    stream,
+                    "HTTP/1.1 {status} X\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .unwrap();
+            }
+        });
+
+        (base_url, rx)
+    }
+
+    #[test]
+    fn test_fetch() {
+        let (base_url, requests) = serve(vec![(200, "1000\n2000\n")]);
+        let mut fetcher = Fetcher::new(&base_url, "abc\n", Duration::ZERO);
+
+        assert_eq!(fetcher.fetch(1).unwrap(), "1000\n2000\n");
+        let (request, cookie) = requests.recv().unwrap();
+        assert_eq!(request, "GET /2022/day/1/input HTTP/1.1");
+        assert_eq!(cookie, "session=abc");
+    }
+
+    #[test]
+    fn test_fetch_errors() {
+        let (base_url, _requests) = serve(vec![
+            (
+                400,
+                "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n",
+            ),
+            (
+                404,
+                "Please don't repeatedly request this endpoint before it unlocks!\n",
+            ),
+            (200, "<!DOCTYPE html>\n<html></html>\n"),
+            (500, "Internal Server Error\n"),
+        ]);
+        let mut fetcher = Fetcher::new(&base_url, "abc", Duration::ZERO);
+
+        assert!(matches!(fetcher.fetch(1), Err(Error::NotLoggedIn)));
+        assert!(matches!(
+            fetcher.fetch(25),
+            Err(Error::NotFound { day: 25 })
+        ));
+        assert!(matches!(
+            fetcher.fetch(1),
+            Err(Error::Unexpected { status: 200, .. })
+        ));
+        assert!(matches!(
+            fetcher.fetch(1),
+            Err(Error::Unexpected { status: 500, .. })
+        ));
+    }
+
+    #[test]
+    fn test_fetch_cached() {
+        let (base_url, requests) = serve(vec![
+            (200, "input\n"),
+            (200, "new input\n"),
+            (200, "third input\n"),
+        ]);
+        let mut fetcher = Fetcher::new(&base_url, "abc", Duration::ZERO);
+        let dir = std::env::temp_dir().join(format!("aoc-fetch-{}", std::process::id()));
+        let path = dir.join("day01/src/input.txt");
+
+        assert_eq!(
+            fetcher.fetch_cached(1, &path, false).unwrap(),
+            Fetched::Downloaded
+        );
+        assert_eq!(
+            fetcher.fetch_cached(1, &path, false).unwrap(),
+            Fetched::Cached
+        );
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "input\n");
+        assert_eq!(
+            fetcher.fetch_cached(1, &path, true).unwrap(),
+            Fetched::Downloaded
+        );
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "new input\n");
+        assert_eq!(requests.iter().take(2).count(), 2);
+
+        // An empty placeholder doesn't count as cached.
+        std::fs::write(&path, "").unwrap();
+        assert_eq!(
+            fetcher.fetch_cached(1, &path, false).unwrap(),
+            Fetched::Downloaded
+        );
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "third input\n");
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_fetch_error_is_not_cached() {
+        let (base_url, _requests) = serve(vec![(400, "Please log in\n")]);
+        let mut fetcher = Fetcher::new(&base_url, "", Duration::ZERO);
+        let path = std::env::temp_dir().join(format!("aoc-fetch-{}-err.txt", std::process::id()));
+
+        assert!(fetcher.fetch_cached(1, &path, false).is_err());
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_rate_limit() {
+        let (base_url, _requests) = serve(vec![(200, "1\n"), (200, "2\n")]);
+        let mut fetcher = Fetcher::new(&base_url, "abc", Duration::from_millis(200));
+
+        let start = Instant::now();
+        fetcher.fetch(1).unwrap();
+        fetcher.fetch(2).unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(200));
+    }
+}
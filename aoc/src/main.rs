@@ -0,0 +1,255 @@
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::Duration;
+
+use clap::{Parser, Subcommand};
+use common::output::{self, Format, Record};
+
+mod answers;
+mod bench;
+mod fetch;
+mod registry;
+mod scaffold;
+
+#[derive(Parser)]
+#[command(about = "Run Advent of Code 2022 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run the solution for a single day, or every day if none is given.
+    Run {
+        day: Option<u8>,
+
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+
+        /// Input file, or `-` for stdin. Defaults to `dayNN/src/input.txt`.
+        #[arg(long, requires = "day")]
+        input: Option<String>,
+
+        /// Output format: json, csv or text.
+        #[arg(long, default_value = "text")]
+        format: Format,
+    },
+    /// Download puzzle inputs into `dayNN/src/input.txt`, skipping those already there.
+    Fetch {
+        #[arg(required = true)]
+        days: Vec<u8>,
+
+        /// Download again even if the input is already cached.
+        #[arg(long)]
+        force: bool,
+
+        #[arg(long, env = "AOC_BASE_URL", default_value = fetch::DEFAULT_BASE_URL)]
+        base_url: String,
+
+        /// Session cookie. Read from `--session-file` if not set.
+        #[arg(long, env = "AOC_SESSION", hide_env_values = true)]
+        session: Option<String>,
+
+        #[arg(long, default_value = "session")]
+        session_file: PathBuf,
+
+        /// Minimum number of seconds between two requests.
+        #[arg(long, default_value_t = 3)]
+        interval: u64,
+    },
+    /// Time every solution on its sample and real input and print a Markdown table.
+    Bench {
+        day: Option<u8>,
+
+        /// Number of runs to take the median of.
+        #[arg(long, default_value_t = 10)]
+        iterations: usize,
+    },
+    /// Check every solution's answers on its sample and real input against the answers file.
+    Verify {
+        day: Option<u8>,
+
+        /// Store answers that aren't known yet.
+        #[arg(long)]
+        record: bool,
+
+        #[arg(long, default_value = answers::DEFAULT_PATH)]
+        answers: PathBuf,
+    },
+    /// Create a crate for a new day from a template and register it with the runner.
+    New { day: u8 },
+}
+
+/// The requested day, or every registered day.
+fn select(day: Option<u8>) -> Result<Vec<&'static registry::Day>, String> {
+    match day {
+        Some(d) => Ok(vec![
+            registry::get(d).ok_or(format!("day {d} is not implemented"))?
+        ]),
+        None => Ok(registry::DAYS.iter().collect()),
+    }
+}
+
+/// Print the answers as text, or collect them into `records` for the other formats.
+fn run(
+    day: &registry::Day,
+    parts: &[u8],
+    input: Option<&str>,
+    format: Format,
+    records: &mut Vec<Record>,
+) -> Result<(), Box<dyn Error>> {
+    let input = common::input::load(day.day, input)?;
+
+    let report = (day.solve)(&input, parts).map_err(|e| format!("day {:02}: {e}", day.day))?;
+
+    if format != Format::Text {
+        let hash = common::input::hash(&input);
+        records.extend(Record::from_report(day.day, &hash, &report));
+        return Ok(());
+    }
+
+    println!("Day {:02} (parsed in {:?})", day.day, report.parse);
+    print!("{}", output::text(&report, true));
+
+    Ok(())
+}
+
+fn try_main(cli: Cli) -> Result<(), Box<dyn Error>> {
+    match cli.command {
+        Command::Run {
+            day,
+            part,
+            input,
+            format,
+        } => {
+            let parts = match part {
+                Some(p) => vec![p],
+                None => vec![1, 2],
+            };
+            let mut records = vec![];
+            for day in select(day)? {
+                run(day, &parts, input.as_deref(), format, &mut records)?;
+            }
+            match format {
+                Format::Json => print!("{}", output::json(&records)),
+                Format::Csv => print!("{}", output::csv(&records)),
+                Format::Text => {}
+            }
+        }
+        Command::Fetch {
+            days,
+            force,
+            base_url,
+            session,
+            session_file,
+            interval,
+        } => {
+            let session = match session {
+                Some(s) => s,
+                None => std::fs::read_to_string(&session_file)
+                    .map_err(|e| format!("failed to read {}: {e}", session_file.display()))?,
+            };
+            let mut fetcher =
+                fetch::Fetcher::new(&base_url, &session, Duration::from_secs(interval));
+            for day in days {
+                let [path, _] = common::input::default_paths(day);
+                match fetcher.fetch_cached(day, &path, force)? {
+                    fetch::Fetched::Cached => println!("{} (cached)", path.display()),
+                    fetch::Fetched::Downloaded => println!("{}", path.display()),
+                }
+            }
+        }
+        Command::Bench { day, iterations } => {
+            let mut rows = vec![];
+            for day in select(day)? {
+                let mut inputs = vec![("sample", day.sample.to_owned())];
+                if let Ok(input) = common::input::load(day.day, None) {
+                    inputs.push(("input", input));
+                }
+                for (name, input) in inputs {
+                    let timings = bench::measure(day, &input, iterations)
+                        .map_err(|e| format!("day {:02} ({name}): {e}", day.day))?;
+                    rows.push((day.day, name, timings));
+                }
+            }
+            print!("{}", bench::table(&rows));
+        }
+        Command::New { day } => {
+            let root = Path::new(".");
+            for path in scaffold::new_day(root, day)? {
+                println!("{}", path.strip_prefix(root).unwrap_or(&path).display());
+            }
+        }
+        Command::Verify {
+            day,
+            record,
+            answers: path,
+        } => {
+            let mut answers = answers::Answers::load(&path)?;
+            let (mut passed, mut failed, mut unknown, mut recorded) = (0, 0, 0, 0);
+
+            for day in select(day)? {
+                let mut inputs = vec![("sample", day.sample.to_owned())];
+                if let Ok(input) = common::input::load(day.day, None) {
+                    inputs.push(("input", input));
+                }
+                for (name, input) in inputs {
+                    let hash = common::input::hash(&input);
+                    let report = match (day.solve)(&input, &[1, 2]) {
+                        Ok(report) => report,
+                        Err(e) => {
+                            failed += 1;
+                            println!("Day {:02} {name}: FAIL (parse error: {e})", day.day);
+                            continue;
+                        }
+                    };
+
+                    for common::Answer { part, value, .. } in report.answers {
+                        let label = format!("Day {:02} {name} part {part}", day.day);
+                        match answers.check(day.day, &hash, part, &value) {
+                            answers::Status::Pass => {
+                                passed += 1;
+                                println!("{label}: pass");
+                            }
+                            answers::Status::Fail { expected } => {
+                                failed += 1;
+                                println!("{label}: FAIL (expected {expected:?}, got {value:?})");
+                            }
+                            answers::Status::Unknown if record => {
+                                recorded += 1;
+                                answers.insert(day.day, &hash, part, &value);
+                                println!("{label}: recorded {value:?}");
+                            }
+                            answers::Status::Unknown => {
+                                unknown += 1;
+                                println!("{label}: unknown (got {value:?})");
+                            }
+                        }
+                    }
+                }
+            }
+
+            if recorded > 0 {
+                answers.save(&path)?;
+            }
+            println!("{passed} passed, {failed} failed, {unknown} unknown, {recorded} recorded");
+            if failed > 0 {
+                return Err(format!("{failed} checks failed against {}", path.display()).into());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    match try_main(Cli::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use common::{ParseError, Report, Solution};
+
+/// A registered day and its solution.
+pub struct Day {
+    pub day: u8,
+    pub sample: &'static str,
+    pub solve: fn(&str, &[u8]) -> Result<Report, ParseError>,
+}
+
+const fn day<S: Solution>() -> Day {
+    Day {
+        day: S::DAY,
+        sample: S::SAMPLE,
+        solve: common::solve::<S>,
+    }
+}
+
+pub const DAYS: &[Day] = &[
+    day::<day01::Day01>(),
+    day::<day02::Day02>(),
+    day::<day03::Day03>(),
+    day::<day04::Day04>(),
+    day::<day05::Day05>(),
+    day::<day06::Day06>(),
+    day::<day07::Day07>(),
+    day::<day09::Day09>(),
+    day::<day10::Day10>(),
+    day::<day13::Day13>(),
+];
+
+pub fn get(day: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.day == day)
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_days_are_sorted_and_unique() {
+        assert!(super::DAYS.windows(2).all(|w| w[0].day < w[1].day));
+    }
+
+    #[test]
+    fn test_solve() {
+        let day = super::get(6).unwrap();
+        let report = (day.solve)("mjqjpqmgbljsphdztnvjfqwrcgsmlb", &[1, 2]).unwrap();
+        let answers: Vec<_> = report.answers.iter().map(|a| a.value.as_str()).collect();
+        assert_eq!(answers, ["7", "19"]);
+    }
+}
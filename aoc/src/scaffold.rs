@@ -0,0 +1,154 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const CARGO_TOML: &str = include_str!("../templates/Cargo.toml.in");
+const MAIN_RS: &str = include_str!("../templates/main.rs.in");
+const LIB_RS: &str = include_str!("../templates/lib.rs.in");
+
+fn render(template: &str, day: u8) -> String {
+    template
+        .replace("{{day:02}}", &format!("{day:02}"))
+        .replace("{{day}}", &day.to_string())
+}
+
+/// Insert `entry` into the sorted run of lines starting with `prefix`, with the
+/// same indentation. Does nothing if it's already there.
+fn insert_sorted(content: &str, prefix: &str, entry: &str) -> Option<String> {
+    let mut lines: Vec<&str> = content.lines().collect();
+    let entries: Vec<usize> = (0..lines.len())
+        .filter(|&i| lines[i].trim_start().starts_with(prefix))
+        .collect();
+    let (&first, &last) = (entries.first()?, entries.last()?);
+
+    if entries.iter().any(|&i| lines[i].trim() == entry) {
+        return Some(content.to_owned());
+    }
+
+    let indent = &lines[first][..lines[first].len() - lines[first].trim_start().len()];
+    let at = entries
+        .into_iter()
+        .find(|&i| lines[i].trim() > entry)
+        .unwrap_or(last + 1);
+    let line = format!("{indent}{entry}");
+    lines.insert(at, &line);
+
+    let mut output = lines.join("\n");
+    if content.ends_with('\n') {
+        output.push('\n');
+    }
+    Some(output)
+}
+
+/// The files `aoc new` creates or changes, relative to the workspace root.
+pub fn plan(root: &Path, day: u8) -> Result<Vec<(PathBuf, String)>, String> {
+    let name = format!("day{day:02}");
+    let dir = root.join(&name);
+    if dir.exists() {
+        return Err(format!("{} already exists", dir.display()));
+    }
+
+    // Register the crate everywhere the other days are listed.
+    let registrations = [
+        ("Cargo.toml", "\"day", format!("\"{name}\",")),
+        (
+            "aoc/Cargo.toml",
+            "day",
+            format!("{name} = {{ path = \"../{name}\" }}"),
+        ),
+        (
+            "aoc/src/registry.rs",
+            "day::<day",
+            format!("day::<{name}::Day{day:02}>(),"),
+        ),
+        (
+            "aoc/benches/solutions.rs",
+            "bench::<day",
+            format!("bench::<{name}::Day{day:02}>,"),
+        ),
+    ];
+
+    let mut files = vec![
+        (dir.join("Cargo.toml"), render(CARGO_TOML, day)),
+        (dir.join("src/main.rs"), render(MAIN_RS, day)),
+        (dir.join("src/lib.rs"), render(LIB_RS, day)),
+        // An empty placeholder for the puzzle input, until it's fetched.
+        (dir.join("src/input.txt"), String::new()),
+    ];
+    for (path, prefix, entry) in registrations {
+        let path = root.join(path);
+        let content = fs::read_to_string(&path)
+            .map_err(|e| format!("failed to read {}: {e}", path.display()))?;
+        let content = insert_sorted(&content, prefix, &entry)
+            .ok_or_else(|| format!("no days are listed in {}", path.display()))?;
+        files.push((path, content));
+    }
+
+    Ok(files)
+}
+
+/// Create the crate for `day` and register it, returning the files written.
+pub fn new_day(root: &Path, day: u8) -> Result<Vec<PathBuf>, String> {
+    if !(1..=25).contains(&day) {
+        return Err(format!("day must be between 1 and 25, not {day}"));
+    }
+
+    // Check everything before writing anything, to not leave a half-made day.
+    let files = plan(root, day)?;
+    for (path, content) in &files {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("failed to create {}: {e}", parent.display()))?;
+        }
+        fs::write(path, content).map_err(|e| format!("failed to write {}: {e}", path.display()))?;
+    }
+
+    Ok(files.into_iter().map(|(path, _)| path).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::{insert_sorted, plan, render, LIB_RS};
+
+    #[test]
+    fn test_insert_sorted() {
+        let content = "[workspace]\nmembers = [\n    \"aoc\",\n    \"day01\",\n    \"day09\",\n    \"geometry\",\n]\n";
+        assert_eq!(
+            insert_sorted(content, "\"day", "\"day08\",").unwrap(),
+            "[workspace]\nmembers = [\n    \"aoc\",\n    \"day01\",\n    \"day08\",\n    \"day09\",\n    \"geometry\",\n]\n"
+        );
+        assert_eq!(
+            insert_sorted(content, "\"day", "\"day14\",").unwrap(),
+            "[workspace]\nmembers = [\n    \"aoc\",\n    \"day01\",\n    \"day09\",\n    \"day14\",\n    \"geometry\",\n]\n"
+        );
+        assert_eq!(
+            insert_sorted(content, "\"day", "\"day09\",").unwrap(),
+            content
+        );
+        assert_eq!(insert_sorted(content, "bench::<day", "x"), None);
+    }
+
+    #[test]
+    fn test_render() {
+        let lib = render(LIB_RS, 8);
+        assert!(lib.contains("pub struct Day08;"));
+        assert!(lib.contains("const DAY: u8 = 8;"));
+        assert!(!lib.contains("{{"));
+    }
+
+    #[test]
+    fn test_plan() {
+        // Tests run from the crate directory, so the workspace is one level up.
+        let root = Path::new("..");
+        let files = plan(root, 25).unwrap();
+        let registry = files
+            .iter()
+            .find(|(path, _)| path.ends_with("aoc/src/registry.rs"))
+            .unwrap();
+        assert!(registry.1.contains("    day::<day25::Day25>(),\n];"));
+
+        let err = plan(root, 1).unwrap_err();
+        assert_eq!(err, "../day01 already exists");
+    }
+}
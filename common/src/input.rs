@@ -0,0 +1,172 @@
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use sha2::{Digest, Sha256};
+
+#[derive(Debug)]
+pub enum Error {
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    Stdin(std::io::Error),
+    NotFound {
+        day: u8,
+        tried: Vec<PathBuf>,
+    },
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Io { path, source } => write!(f, "failed to read {}: {source}", path.display()),
+            Error::Stdin(e) => write!(f, "failed to read stdin: {e}"),
+            Error::NotFound { day, tried } => {
+                write!(f, "no input found for day {day} (tried ")?;
+                for (i, path) in tried.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", path.display())?;
+                }
+                write!(f, "); pass a path, or `-` to read from stdin")
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } => Some(source),
+            Error::Stdin(e) => Some(e),
+            Error::NotFound { .. } => None,
+        }
+    }
+}
+
+/// Where to read the puzzle input from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Source {
+    Stdin,
+    Path(PathBuf),
+    Default(u8),
+}
+
+impl Source {
+    /// Interpret a command line argument: `-` is stdin, anything else a path,
+    /// and no argument at all falls back to the default location for `day`.
+    pub fn from_arg(day: u8, arg: Option<&str>) -> Self {
+        match arg {
+            Some("-") => Source::Stdin,
+            Some(path) => Source::Path(path.into()),
+            None => Source::Default(day),
+        }
+    }
+
+    pub fn read(&self) -> Result<String, Error> {
+        match self {
+            Source::Stdin => {
+                let mut input = String::new();
+                std::io::stdin()
+                    .read_to_string(&mut input)
+                    .map_err(Error::Stdin)?;
+                Ok(input)
+            }
+            Source::Path(path) => read_path(path.clone()),
+            Source::Default(day) => {
+                let tried = default_paths(*day);
+                match tried.iter().find(|p| is_present(p)) {
+                    Some(path) => read_path(path.clone()),
+                    None => Err(Error::NotFound {
+                        day: *day,
+                        tried: tried.to_vec(),
+                    }),
+                }
+            }
+        }
+    }
+}
+
+fn read_path(path: PathBuf) -> Result<String, Error> {
+    std::fs::read_to_string(&path).map_err(|source| Error::Io { path, source })
+}
+
+/// Check if `path` is a file with some input in it, as opposed to missing or
+/// an empty placeholder.
+pub fn is_present(path: &Path) -> bool {
+    path.metadata().is_ok_and(|m| m.is_file() && m.len() > 0)
+}
+
+/// Default input locations for `day`, relative to either the workspace root
+/// or one of the day crates.
+pub fn default_paths(day: u8) -> [PathBuf; 2] {
+    let relative = PathBuf::from(format!("day{day:02}/src/input.txt"));
+    [relative.clone(), PathBuf::from("..").join(relative)]
+}
+
+/// Hex encoded SHA-256 of `input`, used to tell different inputs apart.
+pub fn hash(input: &str) -> String {
+    format!("{:x}", Sha256::digest(input))
+}
+
+/// Read the input for `day` as selected by an optional command line argument.
+pub fn load(day: u8, arg: Option<&str>) -> Result<String, Error> {
+    Source::from_arg(day, arg).read()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::{Error, Source};
+
+    #[test]
+    fn test_hash() {
+        assert_eq!(
+            super::hash("abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn test_from_arg() {
+        assert_eq!(Source::from_arg(1, Some("-")), Source::Stdin);
+        assert_eq!(
+            Source::from_arg(1, Some("foo.txt")),
+            Source::Path(PathBuf::from("foo.txt"))
+        );
+        assert_eq!(Source::from_arg(7, None), Source::Default(7));
+    }
+
+    #[test]
+    fn test_read_path() {
+        let path = std::env::temp_dir().join(format!("aoc-input-{}.txt", std::process::id()));
+        std::fs::write(&path, "1\n2\n").unwrap();
+        let input = Source::Path(path.clone()).read();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(input.unwrap(), "1\n2\n");
+    }
+
+    #[test]
+    fn test_missing_path() {
+        let err = Source::Path("does/not/exist.txt".into())
+            .read()
+            .unwrap_err();
+        assert!(matches!(err, Error::Io { .. }));
+        assert!(err
+            .to_string()
+            .starts_with("failed to read does/not/exist.txt"));
+    }
+
+    #[test]
+    fn test_missing_default() {
+        // Day 0 never has an input.
+        let err = Source::Default(0).read().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "no input found for day 0 (tried day00/src/input.txt, ../day00/src/input.txt); \
+             pass a path, or `-` to read from stdin"
+        );
+    }
+}
@@ -0,0 +1,170 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::time::{Duration, Instant};
+
+pub mod input;
+pub mod output;
+mod parse;
+
+pub use parse::{parse_lines, ParseError};
+
+/// A single day's puzzle: parse the input once, then solve both parts from it.
+pub trait Solution {
+    const DAY: u8;
+    /// The example input from the puzzle description.
+    const SAMPLE: &'static str;
+
+    type Input<'a>;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError>;
+    fn part1(input: &Self::Input<'_>) -> Self::Answer1;
+    fn part2(input: &Self::Input<'_>) -> Self::Answer2;
+}
+
+#[derive(Clone, Debug)]
+pub struct Answer {
+    pub part: u8,
+    pub value: String,
+    pub elapsed: Duration,
+}
+
+#[derive(Clone, Debug)]
+pub struct Report {
+    pub parse: Duration,
+    pub answers: Vec<Answer>,
+}
+
+/// Parse `input` and solve the requested `parts`, timing each step.
+pub fn solve<S: Solution>(input: &str, parts: &[u8]) -> Result<Report, ParseError> {
+    let start = Instant::now();
+    let parsed = S::parse(input)?;
+    let parse = start.elapsed();
+
+    let answers = parts
+        .iter()
+        .map(|&part| {
+            let start = Instant::now();
+            let value = match part {
+                1 => S::part1(&parsed).to_string(),
+                2 => S::part2(&parsed).to_string(),
+                _ => panic!("invalid part: {part}"),
+            };
+            Answer {
+                part,
+                value,
+                elapsed: start.elapsed(),
+            }
+        })
+        .collect();
+
+    Ok(Report { parse, answers })
+}
+
+fn exit(e: impl Display) -> ! {
+    eprintln!("error: {e}");
+    std::process::exit(1);
+}
+
+/// Command line arguments of the day binaries.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Args {
+    input: Option<String>,
+    format: output::Format,
+}
+
+fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Args, String> {
+    let mut parsed = Args::default();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        if let Some(format) = arg.strip_prefix("--format=") {
+            parsed.format = format.parse()?;
+        } else if arg == "--format" {
+            parsed.format = args.next().ok_or("--format requires a value")?.parse()?;
+        } else if arg.starts_with("--") || parsed.input.is_some() {
+            return Err(format!("unexpected argument {arg:?}"));
+        } else {
+            parsed.input = Some(arg);
+        }
+    }
+    Ok(parsed)
+}
+
+/// Entry point shared by the day binaries.
+pub fn run<S: Solution>() {
+    let args = parse_args(std::env::args().skip(1)).unwrap_or_else(|e| {
+        exit(format!(
+            "{e}\nusage: day{:02} [INPUT | -] [--format json|csv|text]",
+            S::DAY
+        ))
+    });
+    let input = input::load(S::DAY, args.input.as_deref()).unwrap_or_else(|e| exit(e));
+    let report = solve::<S>(&input, &[1, 2]).unwrap_or_else(|e| exit(e));
+
+    let records = || output::Record::from_report(S::DAY, &input::hash(&input), &report);
+    match args.format {
+        output::Format::Json => print!("{}", output::json(&records())),
+        output::Format::Csv => print!("{}", output::csv(&records())),
+        output::Format::Text => print!("{}", output::text(&report, false)),
+    }
+}
+
+/// Entry point of day binaries with subcommands of their own, like
+/// `day01 report`. Anything else is left to [`run`].
+pub fn run_with<S: Solution, C: clap::Subcommand>(f: impl FnOnce(C) -> Result<(), Box<dyn Error>>) {
+    let args: Vec<String> = std::env::args().collect();
+    match args.get(1) {
+        Some(name) if C::has_subcommand(name) => {
+            // Usage messages take the binary's name from `args[0]`.
+            let command = clap::Command::new("day").subcommand_required(true);
+            let matches = C::augment_subcommands(command).get_matches_from(args);
+            let command = C::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+            f(command).unwrap_or_else(|e| exit(e));
+        }
+        _ => run::<S>(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::output::Format;
+    use super::{parse_args, Args};
+
+    fn parse(args: &[&str]) -> Result<Args, String> {
+        parse_args(args.iter().map(|s| s.to_string()))
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(parse(&[]), Ok(Args::default()));
+        assert_eq!(
+            parse(&["-", "--format", "csv"]),
+            Ok(Args {
+                input: Some("-".to_owned()),
+                format: Format::Csv
+            })
+        );
+        assert_eq!(
+            parse(&["--format=json", "input.txt"]),
+            Ok(Args {
+                input: Some("input.txt".to_owned()),
+                format: Format::Json
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_args_error() {
+        assert_eq!(
+            parse(&["--format"]),
+            Err("--format requires a value".to_owned())
+        );
+        assert_eq!(
+            parse(&["a", "b"]),
+            Err("unexpected argument \"b\"".to_owned())
+        );
+        assert!(parse(&["--format", "yaml"]).is_err());
+        assert!(parse(&["--verbose"]).is_err());
+    }
+}
@@ -0,0 +1,187 @@
+use std::fmt::Write;
+
+use serde::Serialize;
+
+use crate::Report;
+
+/// How to print answers.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Format {
+    /// Free text for people, e.g. `Part 1 = 24000`.
+    #[default]
+    Text,
+    /// A JSON array of [`Record`]s.
+    Json,
+    /// A header followed by one line per [`Record`].
+    Csv,
+}
+
+impl std::str::FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err(format!("unknown format {s:?}, expected json, csv or text")),
+        }
+    }
+}
+
+/// One answer, along with where it came from.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Record {
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub elapsed_ns: u128,
+    /// See [`crate::input::hash`].
+    pub input_hash: String,
+}
+
+impl Record {
+    pub fn from_report(day: u8, input_hash: &str, report: &Report) -> Vec<Self> {
+        report
+            .answers
+            .iter()
+            .map(|a| Record {
+                day,
+                part: a.part,
+                answer: a.value.clone(),
+                elapsed_ns: a.elapsed.as_nanos(),
+                input_hash: input_hash.to_owned(),
+            })
+            .collect()
+    }
+}
+
+/// One `Part N = answer` line per answer, followed by how long it took if
+/// `timed`. Multi-line answers (e.g. day 10) are printed below the label.
+pub fn text(report: &Report, timed: bool) -> String {
+    let mut output = String::new();
+    for answer in &report.answers {
+        let elapsed = match timed {
+            true => format!(" ({:?})", answer.elapsed),
+            false => String::new(),
+        };
+        if answer.value.contains('\n') {
+            writeln!(output, "Part {} ={elapsed}\n{}", answer.part, answer.value).unwrap();
+        } else {
+            writeln!(output, "Part {} = {}{elapsed}", answer.part, answer.value).unwrap();
+        }
+    }
+    output
+}
+
+pub fn json(records: &[Record]) -> String {
+    let mut output = serde_json::to_string_pretty(records).unwrap();
+    output.push('\n');
+    output
+}
+
+/// Quote a CSV field if it contains a separator, quote or line break.
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_owned()
+    }
+}
+
+pub fn csv(records: &[Record]) -> String {
+    let mut output = String::from("day,part,answer,elapsed_ns,input_hash\n");
+    for r in records {
+        writeln!(
+            output,
+            "{},{},{},{},{}",
+            r.day,
+            r.part,
+            csv_field(&r.answer),
+            r.elapsed_ns,
+            r.input_hash
+        )
+        .unwrap();
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{csv, json, text, Format, Record};
+    use crate::{Answer, Report};
+
+    fn records() -> Vec<Record> {
+        vec![
+            Record {
+                day: 1,
+                part: 1,
+                answer: "24000".to_owned(),
+                elapsed_ns: 1500,
+                input_hash: "abc".to_owned(),
+            },
+            Record {
+                day: 10,
+                part: 2,
+                answer: "#.\n\"x\",\n".to_owned(),
+                elapsed_ns: 20,
+                input_hash: "def".to_owned(),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_format() {
+        assert_eq!("json".parse(), Ok(Format::Json));
+        assert_eq!(
+            "xml".parse::<Format>(),
+            Err("unknown format \"xml\", expected json, csv or text".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_text() {
+        let report = Report {
+            parse: Duration::ZERO,
+            answers: vec![
+                Answer {
+                    part: 1,
+                    value: "24000".to_owned(),
+                    elapsed: Duration::from_micros(3),
+                },
+                Answer {
+                    part: 2,
+                    value: "#.\n.#".to_owned(),
+                    elapsed: Duration::from_millis(2),
+                },
+            ],
+        };
+        assert_eq!(text(&report, false), "Part 1 = 24000\nPart 2 =\n#.\n.#\n");
+        assert_eq!(
+            text(&report, true),
+            "Part 1 = 24000 (3µs)\nPart 2 = (2ms)\n#.\n.#\n"
+        );
+    }
+
+    #[test]
+    fn test_csv() {
+        assert_eq!(
+            csv(&records()),
+            "day,part,answer,elapsed_ns,input_hash\n\
+             1,1,24000,1500,abc\n\
+             10,2,\"#.\n\"\"x\"\",\n\",20,def\n"
+        );
+    }
+
+    #[test]
+    fn test_json() {
+        let value: serde_json::Value = serde_json::from_str(&json(&records())).unwrap();
+        assert_eq!(value[0]["answer"], "24000");
+        assert_eq!(value[0]["elapsed_ns"], 1500);
+        assert_eq!(value[1]["day"], 10);
+        assert_eq!(value[1]["answer"], "#.\n\"x\",\n");
+        assert_eq!(value[1]["input_hash"], "def");
+    }
+}
@@ -0,0 +1,92 @@
+/// A parse error pointing at a 1-based line and column of the input.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, message: impl Into<String>) -> Self {
+        Self {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+
+    /// An error on a single line; [`parse_lines`] fills in the line number.
+    pub fn column(column: usize, message: impl Into<String>) -> Self {
+        Self::new(1, column, message)
+    }
+
+    /// An error at byte `offset` into `input`.
+    pub fn at(input: &str, offset: usize, message: impl Into<String>) -> Self {
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        Self::new(
+            before.matches('\n').count() + 1,
+            before[line_start..].chars().count() + 1,
+            message,
+        )
+    }
+
+    /// Move the error down by `lines`, for errors found in a later part of the input.
+    pub fn offset(mut self, lines: usize) -> Self {
+        self.line += lines;
+        self
+    }
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parse every line of `input` with `f`, adding the line number to any error.
+pub fn parse_lines<'a, T>(
+    input: &'a str,
+    mut f: impl FnMut(&'a str) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| f(line).map_err(|e| e.offset(i)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_lines, ParseError};
+
+    #[test]
+    fn test_at() {
+        let input = "abc\ndéf\nghi";
+        assert_eq!(ParseError::at(input, 0, "x"), ParseError::new(1, 1, "x"));
+        assert_eq!(ParseError::at(input, 4, "x"), ParseError::new(2, 1, "x"));
+        assert_eq!(ParseError::at(input, 7, "x"), ParseError::new(2, 3, "x"));
+        assert_eq!(ParseError::at(input, 9, "x"), ParseError::new(3, 1, "x"));
+    }
+
+    #[test]
+    fn test_parse_lines() {
+        let parse = |s: &str| {
+            s.parse::<u8>()
+                .map_err(|e| ParseError::column(1, e.to_string()))
+        };
+        assert_eq!(parse_lines("1\n2\n", parse), Ok(vec![1, 2]));
+
+        let err = parse_lines("1\n2\nx\n", parse).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 3, column 1: invalid digit found in string"
+        );
+    }
+}
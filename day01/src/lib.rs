@@ -0,0 +1,459 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use common::{ParseError, Solution};
+use indoc::indoc;
+
+fn parse_line(s: &str) -> Result<u32, ParseError> {
+    s.parse()
+        .map_err(|e| ParseError::column(1, format!("invalid calories {s:?}: {e}")))
+}
+
+/// What a single elf is carrying. Each item fits in a `u32`, but a large
+/// enough inventory doesn't.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Elf {
+    pub items: usize,
+    pub calories: u64,
+}
+
+/// Groups lines into elves, one line at a time, for both [`parse_elves`] and
+/// [`stream`].
+#[derive(Debug, Default)]
+struct Elves {
+    current: Option<Elf>,
+}
+
+impl Elves {
+    /// Add line `i` (from 0) of the input, returning the elf it finishes.
+    fn line(&mut self, i: usize, line: &str) -> Result<Option<Elf>, ParseError> {
+        if line.is_empty() {
+            return Ok(self.current.take());
+        }
+        let calories = parse_line(line).map_err(|e| e.offset(i))?;
+        let elf = self.current.get_or_insert_with(Elf::default);
+        elf.items += 1;
+        elf.calories += u64::from(calories);
+        Ok(None)
+    }
+
+    /// The last elf, if the input doesn't end with a blank line.
+    fn finish(&mut self) -> Option<Elf> {
+        self.current.take()
+    }
+}
+
+/// Every elf's inventory, in order.
+pub fn parse_elves(input: &str) -> Result<Vec<Elf>, ParseError> {
+    let mut elves = vec![];
+    let mut current = Elves::default();
+
+    for (i, line) in input.lines().enumerate() {
+        elves.extend(current.line(i, line)?);
+    }
+    elves.extend(current.finish());
+
+    Ok(elves)
+}
+
+/// Total calories carried by each elf.
+pub fn parse_input(input: &str) -> Result<Vec<u64>, ParseError> {
+    Ok(parse_elves(input)?.iter().map(|elf| elf.calories).collect())
+}
+
+/// The `n` largest totals along with the (0-based) index of the elf carrying
+/// each, largest first. Ties go to the earlier elf.
+pub fn top_n<T: Ord>(totals: impl IntoIterator<Item = T>, n: usize) -> Vec<(usize, T)> {
+    let totals = totals.into_iter();
+    // A min-heap of the best totals so far, so the smallest one is evicted
+    // first. It holds at most `n + 1` totals, and no more than there are.
+    let (len, _) = totals.size_hint();
+    let mut heap = BinaryHeap::with_capacity(n.saturating_add(1).min(len));
+    for (i, total) in totals.enumerate() {
+        heap.push(Reverse((total, Reverse(i))));
+        if heap.len() > n {
+            heap.pop();
+        }
+    }
+    heap.into_sorted_vec()
+        .into_iter()
+        .map(|Reverse((total, Reverse(i)))| (i, total))
+        .collect()
+}
+
+/// Statistics about every elf's inventory.
+pub mod report {
+    use std::fmt::Write;
+
+    use crate::Elf;
+
+    /// The percentiles included in a [`Report`].
+    pub const PERCENTILES: [u8; 5] = [10, 25, 75, 90, 99];
+
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct Report {
+        pub elves: Vec<Elf>,
+        pub items: usize,
+        pub calories: u64,
+        /// Mean calories per elf, or 0 if there are no elves.
+        pub mean: f64,
+        /// Median calories per elf, or 0 if there are no elves.
+        pub median: f64,
+        /// Nearest-rank percentiles of the calories per elf, for each of
+        /// [`PERCENTILES`]. Empty if there are no elves.
+        pub percentiles: Vec<(u8, u64)>,
+        /// The elves carrying the most calories as `(index, calories)`, see
+        /// [`crate::top_n`].
+        pub top: Vec<(usize, u64)>,
+    }
+
+    /// The smallest value that at least `p` percent of `sorted` is less than
+    /// or equal to.
+    pub fn percentile(sorted: &[u64], p: u8) -> Option<u64> {
+        let rank = (usize::from(p.min(100)) * sorted.len()).div_ceil(100);
+        sorted.get(rank.max(1) - 1).copied()
+    }
+
+    fn median(sorted: &[u64]) -> f64 {
+        match sorted.len() {
+            0 => 0.0,
+            n if n % 2 == 1 => sorted[n / 2] as f64,
+            n => (sorted[n / 2 - 1] as f64 + sorted[n / 2] as f64) / 2.0,
+        }
+    }
+
+    impl Report {
+        pub fn new(elves: Vec<Elf>, top: usize) -> Self {
+            let mut sorted: Vec<u64> = elves.iter().map(|elf| elf.calories).collect();
+            sorted.sort_unstable();
+
+            let calories: u64 = sorted.iter().sum();
+            let mean = if elves.is_empty() {
+                0.0
+            } else {
+                calories as f64 / elves.len() as f64
+            };
+
+            Self {
+                items: elves.iter().map(|elf| elf.items).sum(),
+                calories,
+                mean,
+                median: median(&sorted),
+                percentiles: PERCENTILES
+                    .iter()
+                    .filter_map(|&p| Some((p, percentile(&sorted, p)?)))
+                    .collect(),
+                top: crate::top_n(elves.iter().map(|elf| elf.calories), top),
+                elves,
+            }
+        }
+    }
+
+    impl std::fmt::Display for Report {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            writeln!(f, "Elves: {}", self.elves.len())?;
+            writeln!(f, "Items: {}", self.items)?;
+            writeln!(f, "Calories: {}", self.calories)?;
+            writeln!(f, "Mean: {:.1}", self.mean)?;
+            writeln!(f, "Median: {:.1}", self.median)?;
+            let mut percentiles = String::new();
+            for (p, calories) in &self.percentiles {
+                write!(percentiles, " p{p}={calories}")?;
+            }
+            writeln!(f, "Percentiles:{percentiles}")?;
+            writeln!(f, "Top {}:", self.top.len())?;
+            for (i, calories) in &self.top {
+                let items = self.elves[*i].items;
+                writeln!(f, "  elf {}: {calories} calories in {items} items", i + 1)?;
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Read calorie totals one line at a time, for inputs too large to keep in memory.
+pub mod stream {
+    use std::io::BufRead;
+
+    use common::ParseError;
+
+    use crate::Elves;
+
+    #[derive(Debug)]
+    pub enum Error {
+        Io(std::io::Error),
+        Parse(ParseError),
+    }
+
+    impl std::fmt::Display for Error {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Error::Io(e) => write!(f, "failed to read input: {e}"),
+                Error::Parse(e) => e.fmt(f),
+            }
+        }
+    }
+
+    impl std::error::Error for Error {
+        fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+            match self {
+                Error::Io(e) => Some(e),
+                Error::Parse(e) => Some(e),
+            }
+        }
+    }
+
+    /// Total calories carried by each elf, in order. See [`totals`].
+    pub struct Totals<R> {
+        reader: R,
+        line: String,
+        lines_read: usize,
+        elves: Elves,
+        done: bool,
+    }
+
+    /// Total calories carried by each elf in `reader`, like [`crate::parse_input`].
+    pub fn totals<R: BufRead>(reader: R) -> Totals<R> {
+        Totals {
+            reader,
+            line: String::new(),
+            lines_read: 0,
+            elves: Elves::default(),
+            done: false,
+        }
+    }
+
+    impl<R: BufRead> Totals<R> {
+        fn next_total(&mut self) -> Result<Option<u64>, Error> {
+            loop {
+                self.line.clear();
+                if self.reader.read_line(&mut self.line).map_err(Error::Io)? == 0 {
+                    self.done = true;
+                    return Ok(self.elves.finish().map(|elf| elf.calories));
+                }
+                self.lines_read += 1;
+
+                let line = self.line.trim_end_matches('\n').trim_end_matches('\r');
+                let elf = self
+                    .elves
+                    .line(self.lines_read - 1, line)
+                    .map_err(Error::Parse)?;
+                if let Some(elf) = elf {
+                    return Ok(Some(elf.calories));
+                }
+            }
+        }
+    }
+
+    impl<R: BufRead> Iterator for Totals<R> {
+        type Item = Result<u64, Error>;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            if self.done {
+                return None;
+            }
+            let total = self.next_total();
+            if total.is_err() {
+                self.done = true;
+            }
+            total.transpose()
+        }
+    }
+
+    /// The `n` largest totals in `reader` with the index of the elf carrying
+    /// each, largest first, keeping no more than `n` totals in memory.
+    pub fn top_n<R: BufRead>(reader: R, n: usize) -> Result<Vec<(usize, u64)>, Error> {
+        itertools::process_results(totals(reader), |totals| crate::top_n(totals, n))
+    }
+}
+
+const SAMPLE: &str = indoc! {"
+    1000
+    2000
+    3000
+
+    4000
+
+    5000
+    6000
+
+    7000
+    8000
+    9000
+
+    10000
+"};
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+    const SAMPLE: &'static str = SAMPLE;
+
+    type Input<'a> = Vec<u64>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(totals: &Self::Input<'_>) -> u64 {
+        totals.iter().copied().max().unwrap_or(0)
+    }
+
+    fn part2(totals: &Self::Input<'_>) -> u64 {
+        top_n(totals.iter().copied(), 3)
+            .into_iter()
+            .map(|(_, total)| total)
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufReader, Read};
+
+    use common::Solution;
+
+    use crate::report::{percentile, Report};
+    use crate::{parse_elves, stream, top_n, Day01, Elf, SAMPLE};
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(Day01::part1(&Day01::parse(SAMPLE).unwrap()), 24000);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(Day01::part2(&Day01::parse(SAMPLE).unwrap()), 45000);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = crate::parse_input("1000\n\n20x0\n").unwrap_err();
+        assert_eq!(err.line, 3);
+        assert_eq!(
+            err.to_string(),
+            "line 3, column 1: invalid calories \"20x0\": invalid digit found in string"
+        );
+    }
+
+    #[test]
+    fn test_large_totals() {
+        let input = "4294967295\n4294967295\n\n1\n";
+        let totals = Day01::parse(input).unwrap();
+        assert_eq!(totals, [2 * u64::from(u32::MAX), 1]);
+        assert_eq!(Day01::part1(&totals), 8589934590);
+        assert_eq!(Day01::part2(&totals), 8589934591);
+
+        let streamed: Vec<u64> = stream::totals(input.as_bytes())
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(streamed, totals);
+    }
+
+    #[test]
+    fn test_top_n() {
+        let totals = [6000, 4000, 11000, 24000, 10000, 11000];
+        assert_eq!(top_n(totals, 3), [(3, 24000), (2, 11000), (5, 11000)]);
+        assert_eq!(top_n(totals, 0), []);
+        assert_eq!(top_n(totals, 10).len(), 6);
+        assert_eq!(top_n(totals, usize::MAX).len(), 6);
+    }
+
+    #[test]
+    fn test_stream() {
+        let totals: Vec<u64> = stream::totals(SAMPLE.as_bytes())
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(totals, [6000, 4000, 11000, 24000, 10000]);
+
+        let top = stream::top_n("1\r\n\r\n\r\n2\r\n3".as_bytes(), 1).unwrap();
+        assert_eq!(top, [(1, 5)]);
+        // The stream's length isn't known up front.
+        assert_eq!(
+            stream::top_n(SAMPLE.as_bytes(), usize::MAX).unwrap().len(),
+            5
+        );
+    }
+
+    #[test]
+    fn test_stream_error() {
+        let err = stream::top_n("1000\n\n20x0\n".as_bytes(), 3).unwrap_err();
+        assert!(matches!(&err, stream::Error::Parse(e) if e.line == 3));
+
+        let err = stream::top_n(&[b'1', 0xff, b'\n'][..], 3).unwrap_err();
+        assert!(matches!(err, stream::Error::Io(_)));
+    }
+
+    /// An endless inventory where elf `i` carries `i % 1000` calories.
+    struct Inventory(u64, Vec<u8>);
+
+    impl Read for Inventory {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            if self.1.is_empty() {
+                self.1 = format!("{}\n\n", self.0 % 1000).into_bytes();
+                self.0 += 1;
+            }
+            let n = self.1.len().min(buf.len());
+            buf[..n].copy_from_slice(&self.1[..n]);
+            self.1.drain(..n);
+            Ok(n)
+        }
+    }
+
+    #[test]
+    fn test_stream_large() {
+        let reader = BufReader::new(Inventory(0, vec![]).take(1_000_000));
+        let top = stream::top_n(reader, 3).unwrap();
+        assert_eq!(top, [(999, 999), (1999, 999), (2999, 999)]);
+    }
+
+    #[test]
+    fn test_percentile() {
+        let sorted = [15, 20, 35, 40, 50];
+        assert_eq!(percentile(&sorted, 0), Some(15));
+        assert_eq!(percentile(&sorted, 30), Some(20));
+        assert_eq!(percentile(&sorted, 40), Some(20));
+        assert_eq!(percentile(&sorted, 50), Some(35));
+        assert_eq!(percentile(&sorted, 100), Some(50));
+        assert_eq!(percentile(&[], 50), None);
+    }
+
+    #[test]
+    fn test_report() {
+        let elves = parse_elves(SAMPLE).unwrap();
+        assert_eq!(
+            elves[0],
+            Elf {
+                items: 3,
+                calories: 6000
+            }
+        );
+
+        let report = Report::new(elves, 2);
+        assert_eq!(report.items, 10);
+        assert_eq!(report.calories, 55000);
+        assert_eq!(report.mean, 11000.0);
+        assert_eq!(report.median, 10000.0);
+        assert_eq!(report.percentiles[0], (10, 4000));
+        assert_eq!(report.top, [(3, 24000), (2, 11000)]);
+        assert_eq!(
+            report.to_string(),
+            "Elves: 5\n\
+             Items: 10\n\
+             Calories: 55000\n\
+             Mean: 11000.0\n\
+             Median: 10000.0\n\
+             Percentiles: p10=4000 p25=6000 p75=11000 p90=24000 p99=24000\n\
+             Top 2:\n  \
+               elf 4: 24000 calories in 3 items\n  \
+               elf 3: 11000 calories in 2 items\n"
+        );
+
+        let report = Report::new(vec![], 3);
+        assert_eq!((report.mean, report.median), (0.0, 0.0));
+        assert!(report.percentiles.is_empty() && report.top.is_empty());
+    }
+}
@@ -1,59 +1,33 @@
-use itertools::Itertools;
-
-fn parse_line(s: &str) -> u32 {
-    s.parse().unwrap()
-}
-
-fn parse_input(input: &str) -> impl Iterator<Item = u32> + '_ {
-    input.split("\n\n").map(|s| s.lines().map(parse_line).sum())
-}
-
-fn part2(input: &str) -> u32 {
-    parse_input(input)
-        .sorted_unstable_by(|a, b| b.cmp(a)) // Descending.
-        .take(3)
-        .sum()
+use std::error::Error;
+
+use clap::Subcommand;
+use common::Solution;
+use day01::report::Report;
+use day01::Day01;
+
+#[derive(Subcommand)]
+enum Command {
+    /// Print statistics about every elf's inventory instead of the puzzle
+    /// answers.
+    Report {
+        /// Input file, or `-` for stdin.
+        input: Option<String>,
+
+        /// Number of elves carrying the most calories to list.
+        #[arg(long, default_value_t = 3)]
+        top: usize,
+    },
 }
 
-fn part1(input: &str) -> u32 {
-    parse_input(input).max().unwrap_or(0)
+fn report(input: Option<&str>, top: usize) -> Result<(), Box<dyn Error>> {
+    let input = common::input::load(Day01::DAY, input)?;
+    let elves = day01::parse_elves(&input)?;
+    print!("{}", Report::new(elves, top));
+    Ok(())
 }
 
 fn main() {
-    let input = include_str!("input.txt");
-
-    println!("Part 1 = {}", part1(input));
-    println!("Part 2 = {}", part2(input));
-}
-
-#[cfg(test)]
-mod tests {
-    use indoc::indoc;
-
-    const SAMPLE: &str = indoc! {"
-        1000
-        2000
-        3000
-
-        4000
-
-        5000
-        6000
-
-        7000
-        8000
-        9000
-
-        10000
-    "};
-
-    #[test]
-    fn test_part1() {
-        assert_eq!(crate::part1(SAMPLE), 24000);
-    }
-
-    #[test]
-    fn test_part2() {
-        assert_eq!(crate::part2(SAMPLE), 45000);
-    }
+    common::run_with::<Day01, _>(|command| match command {
+        Command::Report { input, top } => report(input.as_deref(), top),
+    });
 }
@@ -0,0 +1,202 @@
+use std::collections::HashMap;
+
+use crate::{Rules, Shape};
+
+/// The shape scoring the most against `opponent`, and its score. Ties go to
+/// the shape that comes first in the rules.
+pub fn best_response(opponent: Shape<'_>) -> (Shape<'_>, u32) {
+    best(opponent.rules(), |s| s.play(opponent))
+}
+
+/// The best response to every round, and the total score from playing them.
+pub fn best_responses<'r>(opponents: &[Shape<'r>]) -> (Vec<Shape<'r>>, u32) {
+    let mut total = 0;
+    let responses = opponents
+        .iter()
+        .map(|&opponent| {
+            let (shape, score) = best_response(opponent);
+            total += score;
+            shape
+        })
+        .collect();
+    (responses, total)
+}
+
+fn best<'r, T: PartialOrd + Copy>(
+    rules: &'r Rules,
+    score: impl Fn(Shape<'r>) -> T,
+) -> (Shape<'r>, T) {
+    let mut shapes = rules.shapes().map(|s| (s, score(s)));
+    let first = shapes.next().expect("rules have at least 3 shapes");
+    shapes.fold(
+        first,
+        |best, next| if next.1 > best.1 { next } else { best },
+    )
+}
+
+/// How likely the opponent is to play each shape.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Distribution<'r> {
+    rules: &'r Rules,
+    probabilities: Vec<f64>,
+}
+
+impl<'r> Distribution<'r> {
+    /// Normalise `weights` into probabilities. Shapes that aren't mentioned
+    /// are never played.
+    pub fn new(weights: &[(Shape<'r>, f64)]) -> Result<Self, String> {
+        let rules = match weights.first() {
+            Some((s, _)) => s.rules(),
+            None => return Err("expected at least one shape".to_owned()),
+        };
+        let mut probabilities = vec![0.0; rules.shapes().count()];
+        for &(shape, weight) in weights {
+            if !(weight.is_finite() && weight >= 0.0) {
+                return Err(format!("invalid weight {weight} for {shape}"));
+            }
+            if !std::ptr::eq(shape.rules(), rules) {
+                return Err(format!("{shape} is from different rules"));
+            }
+            probabilities[shape.index()] += weight;
+        }
+
+        let sum: f64 = probabilities.iter().sum();
+        if sum <= 0.0 {
+            return Err("weights must not all be zero".to_owned());
+        }
+        for p in &mut probabilities {
+            *p /= sum;
+        }
+        Ok(Self {
+            rules,
+            probabilities,
+        })
+    }
+
+    /// How often each shape occurs in `opponents`.
+    pub fn observed(opponents: &[Shape<'r>]) -> Result<Self, String> {
+        let mut counts: HashMap<Shape<'r>, f64> = HashMap::new();
+        for &s in opponents {
+            *counts.entry(s).or_default() += 1.0;
+        }
+        let mut weights: Vec<_> = counts.into_iter().collect();
+        weights.sort_unstable_by_key(|(s, _)| s.index());
+        Self::new(&weights)
+    }
+
+    /// Parse `Rock=2,Paper=1` style weights, by shape name.
+    pub fn parse(rules: &'r Rules, s: &str) -> Result<Self, String> {
+        let weights = s
+            .split(',')
+            .map(|pair| {
+                let (name, weight) = pair
+                    .split_once('=')
+                    .ok_or(format!("expected `shape=weight`, found {pair:?}"))?;
+                let shape = rules
+                    .shape_named(name.trim())
+                    .ok_or(format!("unknown shape {name:?}"))?;
+                let weight = weight
+                    .trim()
+                    .parse()
+                    .map_err(|e| format!("invalid weight {weight:?}: {e}"))?;
+                Ok((shape, weight))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        Self::new(&weights)
+    }
+
+    pub fn probability(&self, shape: Shape<'r>) -> f64 {
+        self.probabilities[shape.index()]
+    }
+
+    /// The shape at `u` in `[0, 1)` of the cumulative distribution, to pick
+    /// shapes at random.
+    pub fn sample(&self, u: f64) -> Shape<'r> {
+        let mut total = 0.0;
+        let mut last = 0;
+        for (i, &p) in self.probabilities.iter().enumerate() {
+            if p == 0.0 {
+                continue;
+            }
+            total += p;
+            last = i;
+            if u < total {
+                break;
+            }
+        }
+        // Rounding can leave `total` just short of 1.
+        self.rules.shapes().nth(last).unwrap()
+    }
+
+    /// The average score of always playing `shape`.
+    pub fn expected_score(&self, shape: Shape<'r>) -> f64 {
+        self.rules
+            .shapes()
+            .map(|opponent| self.probability(opponent) * f64::from(shape.play(opponent)))
+            .sum()
+    }
+
+    /// The shape with the highest expected score, and that score.
+    pub fn best_response(&self) -> (Shape<'r>, f64) {
+        best(self.rules, |s| self.expected_score(s))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{best_response, best_responses, Distribution};
+    use crate::Rules;
+
+    #[test]
+    fn test_best_response() {
+        let rules = Rules::classic();
+        let shape = |name| rules.shape_named(name).unwrap();
+
+        assert_eq!(best_response(shape("Rock")), (shape("Paper"), 8));
+        assert_eq!(best_response(shape("Scissors")), (shape("Rock"), 7));
+
+        let (responses, total) = best_responses(&[shape("Rock"), shape("Paper")]);
+        assert_eq!(responses, [shape("Paper"), shape("Scissors")]);
+        assert_eq!(total, 8 + 9);
+
+        let rpsls = Rules::rpsls();
+        let (best, score) = best_response(rpsls.shape_named("Rock").unwrap());
+        // Spock and Paper both beat Rock, and Spock is worth more.
+        assert_eq!((best.name(), score), ("Spock", 11));
+    }
+
+    #[test]
+    fn test_distribution() {
+        let rules = Rules::classic();
+        let shape = |name| rules.shape_named(name).unwrap();
+
+        let d = Distribution::parse(rules, "Rock=1, Paper=1, Scissors=2").unwrap();
+        assert_eq!(d.probability(shape("Scissors")), 0.5);
+        // 1/4 of draws, 1/4 of losses and 1/2 of wins, plus 1 for Rock.
+        assert_eq!(
+            d.expected_score(shape("Rock")),
+            0.25 * 3.0 + 0.5 * 6.0 + 1.0
+        );
+        assert_eq!(d.best_response(), (shape("Scissors"), 6.0));
+
+        let d = Distribution::observed(&[shape("Paper"), shape("Paper"), shape("Rock")]).unwrap();
+        assert_eq!(d.probability(shape("Paper")), 2.0 / 3.0);
+        assert_eq!(d.best_response().0, shape("Scissors"));
+
+        assert_eq!(
+            Distribution::parse(rules, "Rock=0"),
+            Err("weights must not all be zero".to_owned())
+        );
+        assert_eq!(
+            Distribution::parse(rules, "Stone=1"),
+            Err("unknown shape \"Stone\"".to_owned())
+        );
+        let d = Distribution::parse(rules, "Rock=1,Scissors=3").unwrap();
+        assert_eq!(d.sample(0.0), shape("Rock"));
+        assert_eq!(d.sample(0.25), shape("Scissors"));
+        assert_eq!(d.sample(0.999), shape("Scissors"));
+
+        assert!(Distribution::parse(rules, "Rock=-1").is_err());
+        assert!(Distribution::observed(&[]).is_err());
+    }
+}
@@ -0,0 +1,278 @@
+use common::{parse_lines, Solution};
+pub use rules::Rules;
+
+pub mod analysis;
+mod rules;
+pub mod tournament;
+
+#[derive(Debug)]
+pub enum ParseError {
+    UnknownValue(String),
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::UnknownValue(s) => write!(f, "failed to parse unknown value: {s}"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Outcome {
+    Win,
+    Loss,
+    Draw,
+}
+
+impl Outcome {
+    pub const ALL: [Self; 3] = [Self::Loss, Self::Draw, Self::Win];
+
+    pub(crate) fn index(self) -> usize {
+        match self {
+            Self::Loss => 0,
+            Self::Draw => 1,
+            Self::Win => 2,
+        }
+    }
+
+    /// The score under the classic rules; see [`Rules::outcome_score`] for others.
+    pub fn score(self) -> u32 {
+        Rules::classic().outcome_score(self)
+    }
+}
+
+impl std::str::FromStr for Outcome {
+    type Err = ParseError;
+
+    /// Read an outcome letter under the classic rules.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Rules::classic()
+            .outcome(s)
+            .ok_or_else(|| ParseError::UnknownValue(s.to_owned()))
+    }
+}
+
+/// A shape in some [`Rules`], which it borrows.
+#[derive(Clone, Copy)]
+pub struct Shape<'r> {
+    rules: &'r Rules,
+    index: usize,
+}
+
+impl<'r> Shape<'r> {
+    pub(crate) fn new(rules: &'r Rules, index: usize) -> Self {
+        Self { rules, index }
+    }
+
+    pub fn rules(self) -> &'r Rules {
+        self.rules
+    }
+
+    /// Position in the cyclic order of the rules.
+    pub fn index(self) -> usize {
+        self.index
+    }
+
+    pub fn name(self) -> &'r str {
+        self.rules.shape_name(self.index)
+    }
+
+    pub fn score(self) -> u32 {
+        self.rules.shape_score(self.index)
+    }
+
+    /// Score for playing this shape against `other`.
+    pub fn play(self, other: Self) -> u32 {
+        self.score() + self.rules.outcome_score(self.cmp(other))
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn cmp(self, other: Self) -> Outcome {
+        debug_assert!(std::ptr::eq(self.rules, other.rules));
+        let n = self.rules.shape_count();
+        match (self.index + n - other.index) % n {
+            0 => Outcome::Draw,
+            d if d <= n / 2 => Outcome::Win,
+            _ => Outcome::Loss,
+        }
+    }
+
+    /// The shape right before this one, which it beats.
+    pub fn beats(self) -> Self {
+        self.offset(-1)
+    }
+
+    /// The shape right after this one, which beats it.
+    pub fn beaten_by(self) -> Self {
+        self.offset(1)
+    }
+
+    fn offset(self, by: isize) -> Self {
+        let n = self.rules.shape_count() as isize;
+        Self::new(
+            self.rules,
+            (self.index as isize + by).rem_euclid(n) as usize,
+        )
+    }
+}
+
+impl PartialEq for Shape<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self.rules, other.rules) && self.index == other.index
+    }
+}
+
+impl Eq for Shape<'_> {}
+
+impl std::hash::Hash for Shape<'_> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.index.hash(state);
+    }
+}
+
+impl std::fmt::Debug for Shape<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl std::fmt::Display for Shape<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl std::str::FromStr for Shape<'static> {
+    type Err = ParseError;
+
+    /// Read a shape letter under the classic rules; see [`Rules::shape`] for others.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Rules::classic()
+            .shape(s)
+            .ok_or_else(|| ParseError::UnknownValue(s.to_owned()))
+    }
+}
+
+pub fn parse_line<T, U>(s: &str) -> Result<(T, U), common::ParseError>
+where
+    T: std::str::FromStr,
+    T::Err: std::fmt::Display,
+    U: std::str::FromStr,
+    U::Err: std::fmt::Display,
+{
+    let (a, b) = s
+        .split_once(' ')
+        .ok_or_else(|| common::ParseError::column(1, "expected two values separated by a space"))?;
+    let a = a
+        .parse::<T>()
+        .map_err(|e| common::ParseError::column(1, e.to_string()))?;
+    let b = b
+        .parse::<U>()
+        .map_err(|e| common::ParseError::column(s.len() - b.len() + 1, e.to_string()))?;
+    Ok((a, b))
+}
+
+const SAMPLE: &str = "A Y\nB X\nC Z\n";
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+    const SAMPLE: &'static str = SAMPLE;
+
+    /// The second column is read both as a shape (part 1) and as an outcome (part 2).
+    type Input<'a> = (
+        Vec<(Shape<'static>, Shape<'static>)>,
+        Vec<(Shape<'static>, Outcome)>,
+    );
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, common::ParseError> {
+        Ok((
+            parse_lines(input, parse_line::<Shape, Shape>)?,
+            parse_lines(input, parse_line::<Shape, Outcome>)?,
+        ))
+    }
+
+    fn part1((rounds, _): &Self::Input<'_>) -> u32 {
+        rounds.iter().fold(0, |score, &(a, b)| score + b.play(a))
+    }
+
+    fn part2((_, rounds): &Self::Input<'_>) -> u32 {
+        rounds.iter().fold(0, |score, (a, o)| {
+            let b = match o {
+                Outcome::Win => a.beaten_by(),
+                Outcome::Loss => a.beats(),
+                Outcome::Draw => *a,
+            };
+            score + b.play(*a)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    fn shape(name: &str) -> Shape<'static> {
+        Rules::classic().shape_named(name).unwrap()
+    }
+
+    #[test]
+    fn test_parse_line_shape_shape() {
+        let result = SAMPLE
+            .lines()
+            .map(|s| parse_line::<Shape, Shape>(s).unwrap())
+            .collect::<Vec<_>>();
+        let expect = [
+            (shape("Rock"), shape("Paper")),
+            (shape("Paper"), shape("Rock")),
+            (shape("Scissors"), shape("Scissors")),
+        ];
+        assert_eq!(result, expect);
+    }
+
+    #[test]
+    fn test_parse_line_shape_outcome() {
+        let result = SAMPLE
+            .lines()
+            .map(|s| parse_line::<Shape, Outcome>(s).unwrap())
+            .collect::<Vec<_>>();
+        let expect = [
+            (shape("Rock"), Outcome::Draw),
+            (shape("Paper"), Outcome::Loss),
+            (shape("Scissors"), Outcome::Win),
+        ];
+
+        assert_eq!(result, expect);
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(Day02::part1(&Day02::parse(SAMPLE).unwrap()), 15);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(Day02::part2(&Day02::parse(SAMPLE).unwrap()), 12);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = Day02::parse("A Y\nB W\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 3: failed to parse unknown value: W"
+        );
+
+        let err = Day02::parse("AY\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 1: expected two values separated by a space"
+        );
+    }
+}
@@ -1,187 +1,120 @@
-#[derive(Debug)]
-enum ParseError {
-    UnknownValue(String),
+use std::error::Error;
+
+use clap::Subcommand;
+use common::Solution;
+use day02::analysis::{self, Distribution};
+use day02::tournament::{self, Player, Strategy};
+use day02::{Day02, Rules};
+
+#[derive(Subcommand)]
+enum Command {
+    /// Print the best responses to the opponent's column, and the expected
+    /// score of each shape against the opponent.
+    Analyse {
+        /// Input file, or `-` for stdin.
+        input: Option<String>,
+
+        /// How often the opponent plays each shape, e.g. `Rock=2,Paper=1`.
+        /// Defaults to the opponent's observed play.
+        #[arg(long, value_name = "WEIGHTS")]
+        against: Option<String>,
+    },
+    /// Play strategy guides, and optionally random and counter players,
+    /// against each other and print a leaderboard.
+    Tournament {
+        /// Strategy guide files, or `-` for stdin.
+        guides: Vec<String>,
+
+        #[arg(long, default_value_t = 1000)]
+        rounds: usize,
+
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+
+        /// Add a player picking shapes at random with these weights, e.g.
+        /// `Rock=2,Paper=1`.
+        #[arg(long, value_name = "WEIGHTS")]
+        random: Vec<String>,
+
+        /// Add a player countering its opponent's previous shape.
+        #[arg(long)]
+        counter: bool,
+    },
 }
 
-impl std::fmt::Display for ParseError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            ParseError::UnknownValue(s) => write!(f, "failed to parse unknown value: {s}"),
-        }
+fn analyse(input: Option<&str>, against: Option<&str>) -> Result<(), Box<dyn Error>> {
+    let input = common::input::load(Day02::DAY, input)?;
+    let parsed = Day02::parse(&input)?;
+    let opponents: Vec<_> = parsed.0.iter().map(|&(a, _)| a).collect();
+
+    let (_, best) = analysis::best_responses(&opponents);
+    println!("Strategy guide: {}", Day02::part1(&parsed));
+    println!("Best responses: {best}");
+
+    let distribution = match against {
+        Some(weights) => Distribution::parse(Rules::classic(), weights)?,
+        None => Distribution::observed(&opponents)?,
+    };
+    let odds: Vec<String> = Rules::classic()
+        .shapes()
+        .map(|shape| format!("{shape} {:.1}%", distribution.probability(shape) * 100.0))
+        .collect();
+    println!("Opponent: {}", odds.join(", "));
+    println!("Expected score per round:");
+    for shape in Rules::classic().shapes() {
+        println!("  {shape}: {:.3}", distribution.expected_score(shape));
     }
+    let (shape, score) = distribution.best_response();
+    println!("Best response: {shape} ({score:.3} per round)");
+    Ok(())
 }
 
-impl std::error::Error for ParseError {}
-
-#[derive(Debug, PartialEq)]
-enum Outcome {
-    Win,
-    Loss,
-    Draw,
-}
-
-impl Outcome {
-    fn score(self) -> u32 {
-        match self {
-            Self::Win => 6,
-            Self::Loss => 0,
-            Self::Draw => 3,
-        }
-    }
-}
-
-impl std::str::FromStr for Outcome {
-    type Err = ParseError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "X" => Ok(Self::Loss),
-            "Y" => Ok(Self::Draw),
-            "Z" => Ok(Self::Win),
-            v => Err(ParseError::UnknownValue(v.to_owned())),
-        }
-    }
-}
-
-#[derive(Clone, Copy, Debug, PartialEq)]
-enum Shape {
-    Rock = 0,
-    Paper = 1,
-    Scissor = 2,
-}
-
-impl Shape {
-    fn score(self) -> u32 {
-        match self {
-            Shape::Rock => 1,
-            Shape::Paper => 2,
-            Shape::Scissor => 3,
-        }
-    }
-
-    fn cmp(self, other: Self) -> Outcome {
-        match ((self as i8) - (other as i8)).rem_euclid(3) {
-            0 => Outcome::Draw,
-            1 => Outcome::Win,
-            2 => Outcome::Loss,
-            _ => unreachable!(),
-        }
-    }
-
-    fn beats(self) -> Self {
-        (self as i8 - 1).rem_euclid(3).into()
+/// The guides play first, then the random players, then the counter player.
+fn tournament(
+    guides: &[String],
+    rounds: usize,
+    seed: u64,
+    random: &[String],
+    counter: bool,
+) -> Result<(), Box<dyn Error>> {
+    let mut players = vec![];
+    for path in guides {
+        let input = common::input::load(Day02::DAY, Some(path))?;
+        players.push(Player {
+            name: path.to_owned(),
+            strategy: Strategy::guide(&input).map_err(|e| format!("{path}: {e}"))?,
+        });
     }
-
-    fn beaten_by(self) -> Self {
-        (self as i8 + 1).rem_euclid(3).into()
+    for weights in random {
+        players.push(Player {
+            name: format!("random({weights})"),
+            strategy: Strategy::Random(Distribution::parse(Rules::classic(), weights)?),
+        });
     }
-}
-
-impl From<i8> for Shape {
-    fn from(n: i8) -> Self {
-        match n {
-            0 => Shape::Rock,
-            1 => Shape::Paper,
-            2 => Shape::Scissor,
-            _ => unreachable!(),
-        }
+    if counter {
+        players.push(Player {
+            name: "counter".to_owned(),
+            strategy: Strategy::Counter(Rules::classic().shapes().next().unwrap()),
+        });
     }
-}
-
-impl std::str::FromStr for Shape {
-    type Err = ParseError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "A" | "X" => Ok(Self::Rock),
-            "B" | "Y" => Ok(Self::Paper),
-            "C" | "Z" => Ok(Self::Scissor),
-            v => Err(ParseError::UnknownValue(v.to_owned())),
-        }
+    if players.len() < 2 {
+        return Err("expected at least two players".into());
     }
-}
-
-fn parse_line<T, U>(s: &str) -> (T, U)
-where
-    T: std::str::FromStr,
-    T::Err: std::fmt::Debug,
-    U: std::str::FromStr,
-    U::Err: std::fmt::Debug,
-{
-    let (a, b) = s.split_once(' ').unwrap();
-    (a.parse().unwrap(), b.parse().unwrap())
-}
 
-fn part1(input: &str) -> u32 {
-    input
-        .lines()
-        .map(parse_line::<Shape, Shape>)
-        .fold(0, |score, (a, b)| score + b.score() + b.cmp(a).score())
-}
-
-fn part2(input: &str) -> u32 {
-    input
-        .lines()
-        .map(parse_line::<Shape, Outcome>)
-        .fold(0, |score, (a, o)| {
-            let b = match o {
-                Outcome::Win => a.beaten_by(),
-                Outcome::Loss => a.beats(),
-                Outcome::Draw => a,
-            };
-            score + b.score() + b.cmp(a).score()
-        })
+    let standings = tournament::round_robin(&players, rounds, seed);
+    print!("{}", tournament::leaderboard(&standings));
+    Ok(())
 }
 
 fn main() {
-    let input = include_str!("input.txt");
-
-    println!("Part 1 = {}", part1(input));
-    println!("Part 2 = {}", part2(input));
-}
-
-#[cfg(test)]
-mod tests {
-    use crate::*;
-
-    const SAMPLE: &str = "A Y\nB X\nC Z\n";
-
-    #[test]
-    fn test_parse_line_shape_shape() {
-        let result = SAMPLE
-            .lines()
-            .map(parse_line::<Shape, Shape>)
-            .collect::<Vec<_>>();
-        let expect = [
-            (Shape::Rock, Shape::Paper),
-            (Shape::Paper, Shape::Rock),
-            (Shape::Scissor, Shape::Scissor),
-        ];
-        assert_eq!(result, expect);
-    }
-
-    #[test]
-    fn test_parse_line_shape_outcome() {
-        let result = SAMPLE
-            .lines()
-            .map(parse_line::<Shape, Outcome>)
-            .collect::<Vec<_>>();
-        let expect = [
-            (Shape::Rock, Outcome::Draw),
-            (Shape::Paper, Outcome::Loss),
-            (Shape::Scissor, Outcome::Win),
-        ];
-
-        assert_eq!(result, expect);
-    }
-
-    #[test]
-    fn test_part1() {
-        assert_eq!(part1(SAMPLE), 15);
-    }
-
-    #[test]
-    fn test_part2() {
-        assert_eq!(part2(SAMPLE), 12);
-    }
+    common::run_with::<Day02, _>(|command| match command {
+        Command::Analyse { input, against } => analyse(input.as_deref(), against.as_deref()),
+        Command::Tournament {
+            guides,
+            rounds,
+            seed,
+            random,
+            counter,
+        } => tournament(&guides, rounds, seed, &random, counter),
+    });
 }
@@ -0,0 +1,306 @@
+use std::sync::LazyLock;
+
+use common::ParseError;
+
+use crate::{Outcome, Shape};
+
+/// The original game.
+pub const CLASSIC: &str = "\
+shape Rock 1 A X
+shape Paper 2 B Y
+shape Scissors 3 C Z
+outcome loss 0 X
+outcome draw 3 Y
+outcome win 6 Z
+";
+
+/// Rock-Paper-Scissors-Lizard-Spock, in an order where each shape beats the
+/// two before it.
+pub const RPSLS: &str = "\
+shape Rock 1 A V
+shape Spock 5 E Z
+shape Paper 2 B W
+shape Lizard 4 D Y
+shape Scissors 3 C X
+outcome loss 0 X
+outcome draw 3 Y
+outcome win 6 Z
+";
+
+static CLASSIC_RULES: LazyLock<Rules> = LazyLock::new(|| CLASSIC.parse().unwrap());
+static RPSLS_RULES: LazyLock<Rules> = LazyLock::new(|| RPSLS.parse().unwrap());
+
+#[derive(Debug, PartialEq, Eq)]
+struct ShapeRule {
+    name: String,
+    score: u32,
+    letters: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct OutcomeRule {
+    score: u32,
+    letter: String,
+}
+
+/// A game with any odd number of shapes, each beating the half of the other
+/// shapes that come before it (wrapping around).
+///
+/// Rules are described one per line:
+///
+/// ```text
+/// shape <name> <score> <letter>...
+/// outcome <loss|draw|win> <score> <letter>
+/// ```
+///
+/// Shapes are listed in that cyclic order, and blank lines and lines starting
+/// with `#` are ignored.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Rules {
+    shapes: Vec<ShapeRule>,
+    /// Indexed by [`Outcome::index`].
+    outcomes: [OutcomeRule; 3],
+}
+
+impl Rules {
+    pub fn classic() -> &'static Self {
+        &CLASSIC_RULES
+    }
+
+    pub fn rpsls() -> &'static Self {
+        &RPSLS_RULES
+    }
+
+    pub(crate) fn shape_count(&self) -> usize {
+        self.shapes.len()
+    }
+
+    pub(crate) fn shape_name(&self, index: usize) -> &str {
+        &self.shapes[index].name
+    }
+
+    pub(crate) fn shape_score(&self, index: usize) -> u32 {
+        self.shapes[index].score
+    }
+
+    /// Every shape, in cyclic order.
+    pub fn shapes(&self) -> impl Iterator<Item = Shape<'_>> {
+        (0..self.shapes.len()).map(move |i| Shape::new(self, i))
+    }
+
+    pub fn shape_named(&self, name: &str) -> Option<Shape<'_>> {
+        self.shapes().find(|s| s.name() == name)
+    }
+
+    /// The shape a letter in a strategy guide stands for.
+    pub fn shape(&self, letter: &str) -> Option<Shape<'_>> {
+        self.shapes()
+            .find(|s| self.shapes[s.index()].letters.iter().any(|l| l == letter))
+    }
+
+    /// The outcome a letter in a strategy guide stands for.
+    pub fn outcome(&self, letter: &str) -> Option<Outcome> {
+        Outcome::ALL
+            .into_iter()
+            .find(|o| self.outcomes[o.index()].letter == letter)
+    }
+
+    pub fn outcome_score(&self, outcome: Outcome) -> u32 {
+        self.outcomes[outcome.index()].score
+    }
+}
+
+impl std::str::FromStr for Rules {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut shapes: Vec<ShapeRule> = vec![];
+        let mut outcomes: [Option<OutcomeRule>; 3] = Default::default();
+
+        for (i, line) in s.lines().enumerate() {
+            let line_no = i + 1;
+            let column = |word: &str| word.as_ptr() as usize - line.as_ptr() as usize + 1;
+            let error =
+                |word: &str, message: String| ParseError::new(line_no, column(word), message);
+
+            let mut words = line.split_whitespace();
+            let Some(kind) = words.next().filter(|w| !w.starts_with('#')) else {
+                continue;
+            };
+            let (Some(name), Some(score)) = (words.next(), words.next()) else {
+                return Err(ParseError::new(
+                    line_no,
+                    line.len() + 1,
+                    "expected a name and a score",
+                ));
+            };
+            let score = score
+                .parse()
+                .map_err(|e| error(score, format!("invalid score {score:?}: {e}")))?;
+            let letters: Vec<&str> = words.collect();
+            if letters.is_empty() {
+                return Err(ParseError::new(
+                    line_no,
+                    line.len() + 1,
+                    "expected at least one letter",
+                ));
+            }
+
+            match kind {
+                "shape" => {
+                    if shapes.iter().any(|s| s.name == name) {
+                        return Err(error(name, format!("{name} is already a shape")));
+                    }
+                    for letter in &letters {
+                        if let Some(other) = shapes
+                            .iter()
+                            .find(|s| s.letters.iter().any(|l| l == letter))
+                        {
+                            return Err(error(
+                                letter,
+                                format!("{letter} already stands for {}", other.name),
+                            ));
+                        }
+                    }
+                    shapes.push(ShapeRule {
+                        name: name.to_owned(),
+                        score,
+                        letters: letters.iter().map(|&l| l.to_owned()).collect(),
+                    });
+                }
+                "outcome" => {
+                    let outcome = match name {
+                        "loss" => Outcome::Loss,
+                        "draw" => Outcome::Draw,
+                        "win" => Outcome::Win,
+                        _ => return Err(error(name, "expected loss, draw or win".to_owned())),
+                    };
+                    let [letter] = letters[..] else {
+                        return Err(error(letters[1], "expected a single letter".to_owned()));
+                    };
+                    if outcomes.iter().flatten().any(|o| o.letter == letter) {
+                        return Err(error(
+                            letter,
+                            format!("{letter} already stands for an outcome"),
+                        ));
+                    }
+                    let rule = &mut outcomes[outcome.index()];
+                    if rule.is_some() {
+                        return Err(error(name, format!("{name} is already described")));
+                    }
+                    *rule = Some(OutcomeRule {
+                        score,
+                        letter: letter.to_owned(),
+                    });
+                }
+                _ => return Err(error(kind, "expected `shape` or `outcome`".to_owned())),
+            }
+        }
+
+        let end = s.lines().count() + 1;
+        if shapes.len() < 3 || shapes.len().is_multiple_of(2) {
+            return Err(ParseError::new(
+                end,
+                1,
+                format!(
+                    "expected an odd number of at least 3 shapes, found {}",
+                    shapes.len()
+                ),
+            ));
+        }
+        let [Some(loss), Some(draw), Some(win)] = outcomes else {
+            return Err(ParseError::new(
+                end,
+                1,
+                "expected a loss, a draw and a win outcome",
+            ));
+        };
+
+        Ok(Self {
+            shapes,
+            outcomes: [loss, draw, win],
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Rules;
+    use crate::analysis::best_response;
+    use crate::Outcome;
+
+    #[test]
+    fn test_rpsls() {
+        let rules = Rules::rpsls();
+        let shape = |name| rules.shape_named(name).unwrap();
+
+        // Each shape beats exactly two others.
+        for a in rules.shapes() {
+            assert_eq!(
+                rules.shapes().filter(|&b| a.cmp(b) == Outcome::Win).count(),
+                2
+            );
+        }
+        assert_eq!(shape("Scissors").cmp(shape("Paper")), Outcome::Win);
+        assert_eq!(shape("Scissors").cmp(shape("Lizard")), Outcome::Win);
+        assert_eq!(shape("Spock").cmp(shape("Scissors")), Outcome::Win);
+        assert_eq!(shape("Lizard").cmp(shape("Spock")), Outcome::Win);
+        assert_eq!(shape("Rock").cmp(shape("Spock")), Outcome::Loss);
+        assert_eq!(shape("Paper").cmp(shape("Paper")), Outcome::Draw);
+
+        assert_eq!(rules.shape("D"), Some(shape("Lizard")));
+        assert_eq!(rules.outcome("Z"), Some(Outcome::Win));
+        assert_eq!(shape("Spock").score(), 5);
+    }
+
+    #[test]
+    fn test_local_rules() {
+        // Shapes borrow their rules, which only have to outlive them.
+        let rules: Rules = super::RPSLS.replace("Spock 5", "Spock 9").parse().unwrap();
+        let shape = |name| rules.shape_named(name).unwrap();
+        assert_eq!(best_response(shape("Rock")), (shape("Spock"), 9 + 6));
+        assert_ne!(shape("Rock"), Rules::rpsls().shape_named("Rock").unwrap());
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = "shape Rock 1 A\nshape Paper 2 B\n"
+            .parse::<Rules>()
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 3, column 1: expected an odd number of at least 3 shapes, found 2"
+        );
+
+        let err = "shape Rock 1 A\nshape Paper x B\n"
+            .parse::<Rules>()
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 13: invalid score \"x\": invalid digit found in string"
+        );
+
+        let err = "# Shapes\nshape Rock 1 A\nshape Paper 2 A\n"
+            .parse::<Rules>()
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 3, column 15: A already stands for Rock"
+        );
+
+        let err = super::CLASSIC
+            .replace("outcome win", "outcome tie")
+            .parse::<Rules>()
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 6, column 9: expected loss, draw or win"
+        );
+
+        let err = super::CLASSIC
+            .replace("outcome win 6 Z", "")
+            .parse::<Rules>()
+            .unwrap_err();
+        assert_eq!(err.line, 7);
+    }
+}
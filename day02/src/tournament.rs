@@ -0,0 +1,244 @@
+use std::fmt::Write;
+
+use common::{parse_lines, ParseError};
+
+use crate::analysis::Distribution;
+use crate::{parse_line, Shape};
+
+/// A small SplitMix64 generator, so that a seed plays out the same games on
+/// every platform.
+#[derive(Clone, Debug)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `[0, 1)`.
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum Strategy<'r> {
+    /// Play the second column of a strategy guide line by line, starting over
+    /// at the end.
+    Guide(Vec<Shape<'r>>),
+    /// Pick every shape at random.
+    Random(Distribution<'r>),
+    /// Play what beats the opponent's previous shape, starting with the given one.
+    Counter(Shape<'r>),
+}
+
+impl<'r> Strategy<'r> {
+    /// Read a strategy guide of `A Y`-style lines.
+    pub fn guide(input: &str) -> Result<Self, ParseError> {
+        let rounds = parse_lines(input, parse_line::<Shape, Shape>)?;
+        if rounds.is_empty() {
+            return Err(ParseError::new(1, 1, "expected at least one round"));
+        }
+        Ok(Strategy::Guide(
+            rounds.into_iter().map(|(_, b)| b).collect(),
+        ))
+    }
+
+    fn play(&self, round: usize, opponent_last: Option<Shape<'r>>, rng: &mut Rng) -> Shape<'r> {
+        match self {
+            Strategy::Guide(shapes) => shapes[round % shapes.len()],
+            Strategy::Random(distribution) => distribution.sample(rng.next_f64()),
+            Strategy::Counter(first) => opponent_last.map_or(*first, Shape::beaten_by),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Player<'r> {
+    pub name: String,
+    pub strategy: Strategy<'r>,
+}
+
+/// A player's results over every match, where a match is won by scoring more
+/// over all its rounds.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Standing {
+    pub name: String,
+    pub wins: u32,
+    pub draws: u32,
+    pub losses: u32,
+    pub score: u64,
+}
+
+/// Play `rounds` rounds between `a` and `b`, returning their scores.
+pub fn play_match<'r>(
+    a: &Strategy<'r>,
+    b: &Strategy<'r>,
+    rounds: usize,
+    rng: &mut Rng,
+) -> (u64, u64) {
+    let (mut score_a, mut score_b) = (0, 0);
+    let (mut last_a, mut last_b) = (None, None);
+    for round in 0..rounds {
+        let shape_a = a.play(round, last_b, rng);
+        let shape_b = b.play(round, last_a, rng);
+        score_a += u64::from(shape_a.play(shape_b));
+        score_b += u64::from(shape_b.play(shape_a));
+        (last_a, last_b) = (Some(shape_a), Some(shape_b));
+    }
+    (score_a, score_b)
+}
+
+/// Play every pair of players against each other once and rank them by
+/// matches won, then total score. Each match gets its own generator derived
+/// from `seed`, so results don't depend on the order of play.
+pub fn round_robin(players: &[Player<'_>], rounds: usize, seed: u64) -> Vec<Standing> {
+    let mut standings: Vec<Standing> = players
+        .iter()
+        .map(|p| Standing {
+            name: p.name.clone(),
+            ..Default::default()
+        })
+        .collect();
+
+    for i in 0..players.len() {
+        for j in i + 1..players.len() {
+            let mut rng = Rng::new(seed ^ ((i as u64) << 32 | j as u64));
+            let (a, b) = play_match(&players[i].strategy, &players[j].strategy, rounds, &mut rng);
+            standings[i].score += a;
+            standings[j].score += b;
+            match a.cmp(&b) {
+                std::cmp::Ordering::Greater => {
+                    standings[i].wins += 1;
+                    standings[j].losses += 1;
+                }
+                std::cmp::Ordering::Less => {
+                    standings[i].losses += 1;
+                    standings[j].wins += 1;
+                }
+                std::cmp::Ordering::Equal => {
+                    standings[i].draws += 1;
+                    standings[j].draws += 1;
+                }
+            }
+        }
+    }
+
+    standings.sort_by(|a, b| {
+        (b.wins, b.draws, b.score)
+            .cmp(&(a.wins, a.draws, a.score))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    standings
+}
+
+/// Render the standings as a Markdown table.
+pub fn leaderboard(standings: &[Standing]) -> String {
+    let mut output = String::new();
+    output.push_str("| # | Player | W | D | L | Score |\n");
+    output.push_str("|--:|:-------|--:|--:|--:|------:|\n");
+    for (i, s) in standings.iter().enumerate() {
+        writeln!(
+            output,
+            "| {} | {} | {} | {} | {} | {} |",
+            i + 1,
+            s.name,
+            s.wins,
+            s.draws,
+            s.losses,
+            s.score
+        )
+        .unwrap();
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{leaderboard, play_match, round_robin, Player, Rng, Strategy};
+    use crate::analysis::Distribution;
+    use crate::{Rules, SAMPLE};
+
+    fn players() -> Vec<Player<'static>> {
+        let rules = Rules::classic();
+        let shape = |name| rules.shape_named(name).unwrap();
+        vec![
+            Player {
+                name: "sample".to_owned(),
+                strategy: Strategy::guide(SAMPLE).unwrap(),
+            },
+            Player {
+                name: "rock".to_owned(),
+                strategy: Strategy::Guide(vec![shape("Rock")]),
+            },
+            Player {
+                name: "counter".to_owned(),
+                strategy: Strategy::Counter(shape("Paper")),
+            },
+            Player {
+                name: "random".to_owned(),
+                strategy: Strategy::Random(
+                    Distribution::parse(rules, "Rock=1,Paper=1,Scissors=1").unwrap(),
+                ),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_play_match() {
+        let players = players();
+        let mut rng = Rng::new(0);
+        // Paper, Rock, Scissors against Rock: a win, a draw and a loss, and
+        // the same for Rock.
+        let (a, b) = play_match(&players[0].strategy, &players[1].strategy, 3, &mut rng);
+        assert_eq!((a, b), (8 + 4 + 3, 1 + 4 + 7));
+
+        // Counter plays Paper, then beats Rock every time.
+        let (a, b) = play_match(&players[2].strategy, &players[1].strategy, 10, &mut rng);
+        assert_eq!((a, b), (80, 10));
+    }
+
+    #[test]
+    fn test_round_robin() {
+        let players = players();
+        let standings = round_robin(&players, 100, 42);
+        assert_eq!(standings, round_robin(&players, 100, 42));
+        assert_eq!(
+            standings
+                .iter()
+                .map(|s| s.wins + s.draws + s.losses)
+                .sum::<u32>(),
+            12
+        );
+
+        let table = leaderboard(&standings);
+        assert!(table.starts_with("| # | Player | W | D | L | Score |\n"));
+        assert_eq!(table.lines().count(), 6);
+    }
+
+    #[test]
+    fn test_rng() {
+        // The first output of the reference SplitMix64 for seed 0.
+        let mut rng = Rng::new(0);
+        assert_eq!(rng.next_u64(), 0xe220_a839_7b1d_cdaf);
+        assert!((0..1000).all(|_| (0.0..1.0).contains(&rng.next_f64())));
+    }
+
+    #[test]
+    fn test_guide_error() {
+        let err = Strategy::guide("").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 1: expected at least one round"
+        );
+        assert_eq!(Strategy::guide("A Y\nB Q\n").unwrap_err().line, 2);
+    }
+}
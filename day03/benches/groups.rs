@@ -0,0 +1,48 @@
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use day03::{Alphabet, Rucksacks};
+
+/// Random-looking rucksacks from a fixed seed, so runs are comparable.
+fn input(alphabet: &[char], n: usize) -> String {
+    let mut state: u64 = 0x2022_1203;
+    let mut next = || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        alphabet[state as usize % alphabet.len()]
+    };
+    let mut input = String::new();
+    for _ in 0..n {
+        input.extend((0..24).map(|_| next()));
+        input.push('\n');
+    }
+    input
+}
+
+/// Group priorities of the puzzle's alphabet, which takes the batched path,
+/// and of an alphabet too wide for it.
+fn bench(c: &mut Criterion) {
+    let narrow: Vec<char> = ('a'..='z').chain('A'..='Z').collect();
+    let wide: Vec<char> = narrow.iter().copied().chain('α'..='ω').collect();
+    let rucksacks = 24 * 10_000;
+
+    let mut group = c.benchmark_group("day03");
+    group.throughput(Throughput::Elements(rucksacks as u64));
+
+    for (name, chars) in [("narrow", narrow), ("wide", wide)] {
+        let alphabet = Alphabet::new(chars.iter().copied().zip(1..)).unwrap();
+        let input = input(&chars, rucksacks);
+        let rucksacks = Rucksacks::parse(&alphabet, &input).unwrap();
+        for size in [3, 8] {
+            group.bench_function(BenchmarkId::new(format!("groups of {size}"), name), |b| {
+                b.iter(|| black_box(&rucksacks).group_priorities(size).unwrap())
+            });
+        }
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench);
+criterion_main!(benches);
@@ -0,0 +1,458 @@
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+use common::ParseError;
+
+use crate::SharedError;
+
+static STANDARD: LazyLock<Alphabet> = LazyLock::new(|| {
+    Alphabet::ranked("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ").unwrap()
+});
+
+/// An [`ItemSet`] operation that doesn't fit the sets' width.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SetError {
+    OutOfRange {
+        index: usize,
+        width: usize,
+    },
+    /// Sets with room for different numbers of item types, like sets from
+    /// different alphabets.
+    Width {
+        left: usize,
+        right: usize,
+    },
+}
+
+impl std::fmt::Display for SetError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SetError::OutOfRange { index, width } => write!(
+                f,
+                "item type {index} is out of range, the set has room for {width}"
+            ),
+            SetError::Width { left, right } => write!(
+                f,
+                "sets with room for {left} and {right} item types can't be combined"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SetError {}
+
+/// A set of item types from an [`Alphabet`], of any size. Sets with room for
+/// up to 64 item types, like the puzzle's, are kept in a single word.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ItemSet {
+    width: usize,
+    words: Words,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+enum Words {
+    One(u64),
+    Many(Vec<u64>),
+}
+
+impl Words {
+    fn as_slice(&self) -> &[u64] {
+        match self {
+            Words::One(word) => std::slice::from_ref(word),
+            Words::Many(words) => words,
+        }
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [u64] {
+        match self {
+            Words::One(word) => std::slice::from_mut(word),
+            Words::Many(words) => words,
+        }
+    }
+}
+
+impl ItemSet {
+    /// An empty set with room for `width` item types.
+    pub fn new(width: usize) -> Self {
+        let words = match width {
+            0..=64 => Words::One(0),
+            _ => Words::Many(vec![0; width.div_ceil(64)]),
+        };
+        Self { width, words }
+    }
+
+    /// A set with room for up to 64 item types, from its bits.
+    pub(crate) fn from_word(width: usize, word: u64) -> Self {
+        debug_assert!(width <= 64);
+        Self {
+            width,
+            words: Words::One(word),
+        }
+    }
+
+    /// The bits of a set with room for up to 64 item types.
+    pub(crate) fn as_word(&self) -> Option<u64> {
+        match self.words {
+            Words::One(word) => Some(word),
+            Words::Many(_) => None,
+        }
+    }
+
+    /// How many item types the set has room for.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn insert(&mut self, index: usize) -> Result<(), SetError> {
+        if index >= self.width {
+            return Err(SetError::OutOfRange {
+                index,
+                width: self.width,
+            });
+        }
+        self.words.as_mut_slice()[index / 64] |= 1 << (index % 64);
+        Ok(())
+    }
+
+    pub fn contains(&self, index: usize) -> bool {
+        self.words
+            .as_slice()
+            .get(index / 64)
+            .is_some_and(|w| w & 1 << (index % 64) != 0)
+    }
+
+    pub fn len(&self) -> usize {
+        self.words
+            .as_slice()
+            .iter()
+            .map(|w| w.count_ones() as usize)
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.as_slice().iter().all(|&w| w == 0)
+    }
+
+    /// The highest index in the set.
+    pub fn last(&self) -> Option<usize> {
+        let words = self.words.as_slice();
+        let i = words.iter().rposition(|&w| w != 0)?;
+        Some(i * 64 + 63 - words[i].leading_zeros() as usize)
+    }
+
+    fn combine(&self, other: &Self, f: impl Fn(u64, u64) -> u64) -> Result<Self, SetError> {
+        if self.width != other.width {
+            return Err(SetError::Width {
+                left: self.width,
+                right: other.width,
+            });
+        }
+        let words = match (&self.words, &other.words) {
+            (Words::One(a), Words::One(b)) => Words::One(f(*a, *b)),
+            (a, b) => Words::Many(
+                a.as_slice()
+                    .iter()
+                    .zip(b.as_slice())
+                    .map(|(&a, &b)| f(a, b))
+                    .collect(),
+            ),
+        };
+        Ok(Self {
+            width: self.width,
+            words,
+        })
+    }
+
+    pub fn intersection(&self, other: &Self) -> Result<Self, SetError> {
+        self.combine(other, |a, b| a & b)
+    }
+
+    pub fn union(&self, other: &Self) -> Result<Self, SetError> {
+        self.combine(other, |a, b| a | b)
+    }
+
+    /// The indices of the item types in the set, in order.
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words
+            .as_slice()
+            .iter()
+            .enumerate()
+            .flat_map(|(i, &word)| {
+                let mut word = word;
+                std::iter::from_fn(move || {
+                    (word != 0).then(|| {
+                        let bit = word.trailing_zeros() as usize;
+                        word &= word - 1;
+                        i * 64 + bit
+                    })
+                })
+            })
+    }
+}
+
+/// The item types rucksacks can hold and their priorities. The puzzle's is
+/// [`Alphabet::standard`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Alphabet {
+    items: Vec<(char, u32)>,
+    /// Indices of the ASCII item types, to find them without hashing.
+    ascii: [Option<usize>; 128],
+    /// Indices of the other item types.
+    index: HashMap<char, usize>,
+    /// Whether priorities never go down along the alphabet, so that the last
+    /// item type in a set has the highest priority.
+    ascending: bool,
+}
+
+impl Alphabet {
+    /// An alphabet of `(item type, priority)` pairs. Priorities don't have to
+    /// be unique or in order.
+    pub fn new(items: impl IntoIterator<Item = (char, u32)>) -> Result<Self, String> {
+        let items: Vec<(char, u32)> = items.into_iter().collect();
+        let mut ascii = [None; 128];
+        let mut index = HashMap::new();
+        for (i, &(c, _)) in items.iter().enumerate() {
+            let previous = match ascii.get_mut(c as usize) {
+                Some(slot) => slot.replace(i),
+                None => index.insert(c, i),
+            };
+            if previous.is_some() {
+                return Err(format!("{c:?} is in the alphabet more than once"));
+            }
+        }
+        if items.is_empty() {
+            return Err("expected at least one item type".to_owned());
+        }
+        let ascending = items.windows(2).all(|w| w[0].1 <= w[1].1);
+        Ok(Self {
+            items,
+            ascii,
+            index,
+            ascending,
+        })
+    }
+
+    /// An alphabet where the item types in `chars` have priorities 1, 2, 3
+    /// and so on.
+    pub fn ranked(chars: &str) -> Result<Self, String> {
+        Self::new(chars.chars().zip(1..))
+    }
+
+    /// The puzzle's alphabet, `a-z` then `A-Z`.
+    pub fn standard() -> &'static Self {
+        &STANDARD
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    fn index(&self, item: char) -> Option<usize> {
+        match self.ascii.get(item as usize) {
+            Some(&i) => i,
+            None => self.index.get(&item).copied(),
+        }
+    }
+
+    pub fn priority(&self, item: char) -> Option<u32> {
+        self.index(item).map(|i| self.items[i].1)
+    }
+
+    /// Encode the item types of a rucksack (or compartment).
+    pub fn set(&self, line: &str) -> Result<ItemSet, ParseError> {
+        let mut set = ItemSet::new(self.len());
+        for (i, c) in line.chars().enumerate() {
+            let index = self.index(c).ok_or_else(|| {
+                ParseError::column(i + 1, format!("{c:?} is not in the alphabet"))
+            })?;
+            set.insert(index)
+                .expect("the set has room for the alphabet");
+        }
+        Ok(set)
+    }
+
+    /// Decode the item types in `set`, in alphabet order. Item types past the
+    /// end of the alphabet are ignored.
+    pub fn items<'a>(&'a self, set: &'a ItemSet) -> impl Iterator<Item = char> + 'a {
+        set.iter().map_while(|i| self.items.get(i).map(|&(c, _)| c))
+    }
+
+    /// The one item type in `set`.
+    pub fn shared_item(&self, set: &ItemSet) -> Result<char, SharedError> {
+        let mut items = self.items(set);
+        match (items.next(), items.next()) {
+            (None, _) => Err(SharedError::None),
+            (Some(item), None) => Ok(item),
+            _ => Err(SharedError::Multiple(self.items(set).collect())),
+        }
+    }
+
+    /// The highest priority in `set`, or 0 if it's empty.
+    pub fn highest_priority(&self, set: &ItemSet) -> u32 {
+        match set.last().and_then(|i| self.items.get(i)) {
+            Some(&(_, priority)) if self.ascending => priority,
+            _ => set
+                .iter()
+                .map_while(|i| self.items.get(i).map(|&(_, p)| p))
+                .max()
+                .unwrap_or(0),
+        }
+    }
+
+    /// [`Alphabet::highest_priority`] for a set of up to 64 item types, from
+    /// its bits.
+    pub(crate) fn highest_word_priority(&self, word: u64) -> u32 {
+        match word
+            .checked_ilog2()
+            .and_then(|i| self.items.get(i as usize))
+        {
+            Some(&(_, priority)) if self.ascending => priority,
+            _ => self.highest_priority(&ItemSet::from_word(self.len(), word)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Alphabet, ItemSet, SetError};
+    use crate::{Rucksacks, SharedError};
+
+    #[test]
+    fn test_wide() {
+        // More item types than fit in a `u64`.
+        let chars: String = ('a'..='z')
+            .chain('A'..='Z')
+            .chain('0'..='9')
+            .chain('α'..='ω')
+            .chain("+-*/".chars())
+            .collect();
+        let alphabet = Alphabet::ranked(&chars).unwrap();
+        assert_eq!(alphabet.len(), 26 + 26 + 10 + 25 + 4);
+
+        let rucksacks = Rucksacks::parse(&alphabet, "aω9/\nω1ωZ\n").unwrap();
+        assert_eq!(
+            rucksacks.rucksack_items(),
+            [Err(SharedError::None), Ok('ω')]
+        );
+        let common = alphabet.set("aω9/").unwrap();
+        assert_eq!(
+            alphabet.shared_item(&common),
+            Err(SharedError::Multiple(vec!['a', '9', 'ω', '/']))
+        );
+        assert_eq!(rucksacks.group_priorities(2), Ok(87));
+    }
+
+    #[test]
+    fn test_custom_priorities() {
+        let alphabet = Alphabet::new([('x', 10), ('y', 1), ('#', 5)]).unwrap();
+        assert_eq!(alphabet.priority('#'), Some(5));
+        assert_eq!(alphabet.priority('a'), None);
+        let rucksacks = Rucksacks::parse(&alphabet, "xy#xy#\n").unwrap();
+        assert_eq!(rucksacks.rucksack_priorities(), 10);
+
+        assert_eq!(
+            Alphabet::new([('x', 1), ('x', 2)]),
+            Err("'x' is in the alphabet more than once".to_owned())
+        );
+        assert!(Alphabet::ranked("").is_err());
+    }
+
+    #[test]
+    fn test_unknown_item() {
+        let alphabet = Alphabet::ranked("abc").unwrap();
+        let err = Rucksacks::parse(&alphabet, "abab\naβcc\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 2: 'β' is not in the alphabet"
+        );
+    }
+
+    #[test]
+    fn test_item_set() {
+        let mut a = ItemSet::new(130);
+        a.insert(0).unwrap();
+        a.insert(64).unwrap();
+        a.insert(129).unwrap();
+        let mut b = ItemSet::new(130);
+        b.insert(129).unwrap();
+        assert_eq!(a.iter().collect::<Vec<_>>(), [0, 64, 129]);
+        assert_eq!(a.intersection(&b), Ok(b.clone()));
+        assert_eq!(a.union(&b), Ok(a.clone()));
+        assert!(a.contains(64) && !a.contains(65) && !a.contains(1000));
+        assert!(ItemSet::new(130).is_empty());
+        assert_eq!(a.last(), Some(129));
+        assert_eq!(b.intersection(&ItemSet::new(130)).unwrap().last(), None);
+
+        let mut small = ItemSet::new(52);
+        small.insert(51).unwrap();
+        small.insert(3).unwrap();
+        assert_eq!(small.iter().collect::<Vec<_>>(), [3, 51]);
+        assert_eq!(small.last(), Some(51));
+        assert_eq!(small.len(), 2);
+    }
+
+    #[test]
+    fn test_narrow_and_wide() {
+        // The same rucksacks, with and without room for a 65th item type.
+        let chars: String = ('a'..='z')
+            .chain('A'..='Z')
+            .chain("0123456789+-".chars())
+            .collect();
+        let narrow = Alphabet::ranked(&chars).unwrap();
+        let wide = Alphabet::ranked(&format!("{chars}#")).unwrap();
+        let input = "a1bZ1Z\nZb+Z+c\n7b-7-Q\n";
+        let narrow = Rucksacks::parse(&narrow, input).unwrap();
+        let wide = Rucksacks::parse(&wide, input).unwrap();
+        assert_eq!(narrow.rucksack_items(), wide.rucksack_items());
+        assert_eq!(narrow.rucksack_priorities(), wide.rucksack_priorities());
+        assert_eq!(narrow.group_items(3), wide.group_items(3));
+        assert_eq!(narrow.group_priorities(3), wide.group_priorities(3));
+        assert_eq!(narrow.rucksack_priorities(), 54 + 63 + 64);
+        assert_eq!(narrow.group_priorities(3), Ok(2));
+    }
+
+    #[test]
+    fn test_set_error() {
+        let mut a = ItemSet::new(130);
+        let err = a.insert(130).unwrap_err();
+        assert_eq!(
+            err,
+            SetError::OutOfRange {
+                index: 130,
+                width: 130
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "item type 130 is out of range, the set has room for 130"
+        );
+        assert!(a.is_empty());
+
+        // Same number of words, but not the same width.
+        let b = ItemSet::new(129);
+        assert_eq!(
+            a.intersection(&b),
+            Err(SetError::Width {
+                left: 130,
+                right: 129
+            })
+        );
+        assert!(b.union(&ItemSet::new(52)).is_err());
+    }
+
+    #[test]
+    fn test_foreign_items() {
+        // Decoding skips item types the alphabet doesn't have.
+        let alphabet = Alphabet::ranked("ab").unwrap();
+        let mut set = ItemSet::new(100);
+        set.insert(1).unwrap();
+        set.insert(99).unwrap();
+        assert_eq!(alphabet.items(&set).collect::<String>(), "b");
+        assert_eq!(alphabet.shared_item(&set), Ok('b'));
+        assert_eq!(alphabet.highest_priority(&set), 2);
+    }
+}
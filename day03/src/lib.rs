@@ -0,0 +1,374 @@
+use common::{parse_lines, ParseError, Solution};
+use indoc::indoc;
+
+pub mod alphabet;
+
+pub use alphabet::{Alphabet, ItemSet, SetError};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum SharedError {
+    None,
+    Multiple(Vec<char>),
+}
+
+impl std::fmt::Display for SharedError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SharedError::None => write!(f, "no item type is shared"),
+            SharedError::Multiple(items) => {
+                write!(f, "{} item types are shared: ", items.len())?;
+                for (i, c) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{c:?}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for SharedError {}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum GroupError {
+    ZeroSize,
+    Partial { size: usize, found: usize },
+}
+
+impl std::fmt::Display for GroupError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GroupError::ZeroSize => write!(f, "group size must be at least 1"),
+            GroupError::Partial { size, found } => write!(
+                f,
+                "expected groups of {size}, but the last group only has {found} rucksacks"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for GroupError {}
+
+fn check_groups(len: usize, size: usize) -> Result<(), GroupError> {
+    match (size, len.checked_rem(size)) {
+        (0, _) => Err(GroupError::ZeroSize),
+        (_, Some(0)) => Ok(()),
+        (_, found) => Err(GroupError::Partial {
+            size,
+            found: found.unwrap_or_default(),
+        }),
+    }
+}
+
+/// The item types in the two compartments of each rucksack. Every set is from
+/// the same alphabet, so they can always be combined.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Rucksacks<'a> {
+    alphabet: &'a Alphabet,
+    compartments: Compartments,
+}
+
+/// Alphabets of up to 64 item types, like the puzzle's, keep the sets as bare
+/// words, so that both parts are a few bitwise operations per rucksack.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Compartments {
+    Narrow(Vec<(u64, u64)>),
+    Wide(Vec<(ItemSet, ItemSet)>),
+}
+
+impl<'a> Rucksacks<'a> {
+    /// One rucksack per line, with the same number of items in each half.
+    pub fn parse(alphabet: &'a Alphabet, input: &str) -> Result<Self, ParseError> {
+        let sets = |line: &str| {
+            // Validate the whole line first so that columns are reported
+            // correctly and splitting it below can't land inside a character.
+            alphabet.set(line)?;
+            let len = line.chars().count();
+            if !len.is_multiple_of(2) {
+                return Err(ParseError::column(
+                    len,
+                    "odd number of items, compartments must be the same size",
+                ));
+            }
+            let mid = line.char_indices().nth(len / 2).map_or(0, |(i, _)| i);
+            let (a, b) = line.split_at(mid);
+            Ok((alphabet.set(a)?, alphabet.set(b)?))
+        };
+        let compartments = match alphabet.len() {
+            0..=64 => Compartments::Narrow(parse_lines(input, |line| {
+                let (a, b) = sets(line)?;
+                let word = |s: ItemSet| s.as_word().expect("narrow alphabet");
+                Ok((word(a), word(b)))
+            })?),
+            _ => Compartments::Wide(parse_lines(input, sets)?),
+        };
+        Ok(Self {
+            alphabet,
+            compartments,
+        })
+    }
+
+    pub fn alphabet(&self) -> &'a Alphabet {
+        self.alphabet
+    }
+
+    pub fn len(&self) -> usize {
+        match &self.compartments {
+            Compartments::Narrow(c) => c.len(),
+            Compartments::Wide(c) => c.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn set(&self, word: u64) -> ItemSet {
+        ItemSet::from_word(self.alphabet.len(), word)
+    }
+
+    /// The item types in both compartments of each rucksack.
+    fn shared(&self) -> Vec<ItemSet> {
+        match &self.compartments {
+            Compartments::Narrow(c) => c.iter().map(|&(a, b)| self.set(a & b)).collect(),
+            Compartments::Wide(c) => c
+                .iter()
+                .map(|(a, b)| a.intersection(b).expect("one alphabet"))
+                .collect(),
+        }
+    }
+
+    /// The item types carried by every elf in each whole group of `size`.
+    fn common(&self, size: usize) -> Vec<ItemSet> {
+        match &self.compartments {
+            Compartments::Narrow(c) => c
+                .chunks_exact(size)
+                .map(|group| self.set(group.iter().fold(!0, |common, (a, b)| common & (a | b))))
+                .collect(),
+            Compartments::Wide(c) => c
+                .chunks_exact(size)
+                .map(|group| {
+                    group
+                        .iter()
+                        .map(|(a, b)| a.union(b).expect("one alphabet"))
+                        .reduce(|common, s| common.intersection(&s).expect("one alphabet"))
+                        .expect("groups aren't empty")
+                })
+                .collect(),
+        }
+    }
+
+    /// The item type in both compartments of each rucksack.
+    pub fn rucksack_items(&self) -> Vec<Result<char, SharedError>> {
+        self.shared()
+            .iter()
+            .map(|s| self.alphabet.shared_item(s))
+            .collect()
+    }
+
+    /// The item types carried by every elf in each group of `size`.
+    pub fn groups(&self, size: usize) -> Result<Vec<ItemSet>, GroupError> {
+        check_groups(self.len(), size)?;
+        Ok(self.common(size))
+    }
+
+    /// The item type carried by every elf in each group of `size`.
+    pub fn group_items(&self, size: usize) -> Result<Vec<Result<char, SharedError>>, GroupError> {
+        Ok(self
+            .groups(size)?
+            .iter()
+            .map(|s| self.alphabet.shared_item(s))
+            .collect())
+    }
+
+    /// Sum of the highest priority shared by the compartments of each rucksack.
+    pub fn rucksack_priorities(&self) -> u32 {
+        match &self.compartments {
+            Compartments::Narrow(c) => c
+                .iter()
+                .map(|&(a, b)| self.alphabet.highest_word_priority(a & b))
+                .sum(),
+            Compartments::Wide(_) => self
+                .shared()
+                .iter()
+                .map(|s| self.alphabet.highest_priority(s))
+                .sum(),
+        }
+    }
+
+    /// Sum of the highest priority shared within each whole group of `size`.
+    fn common_priorities(&self, size: usize) -> u32 {
+        match &self.compartments {
+            // Working on several groups side by side instead, to overlap
+            // the work, measured no faster in `benches/groups.rs`.
+            Compartments::Narrow(c) => c
+                .chunks_exact(size)
+                .map(|group| {
+                    let common = group.iter().fold(!0, |common, (a, b)| common & (a | b));
+                    self.alphabet.highest_word_priority(common)
+                })
+                .sum(),
+            Compartments::Wide(_) => self
+                .common(size)
+                .iter()
+                .map(|s| self.alphabet.highest_priority(s))
+                .sum(),
+        }
+    }
+
+    /// Sum of the highest priority shared within each group of `size` rucksacks.
+    pub fn group_priorities(&self, size: usize) -> Result<u32, GroupError> {
+        check_groups(self.len(), size)?;
+        Ok(self.common_priorities(size))
+    }
+}
+
+const SAMPLE: &str = indoc! {"
+    vJrwpWtwJgWrhcsFMMfFFhFp
+    jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+    PmmdzqPrVvPwwTWBwg
+    wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+    ttgJtRGJQctTZtZT
+    CrZsJsPPZsGzwwsLwLmpwMDw
+"};
+
+/// Elves are grouped by three in part 2. `day03 groups --size N` tries other
+/// sizes.
+pub const GROUP_SIZE: usize = 3;
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+    const SAMPLE: &'static str = SAMPLE;
+
+    type Input<'a> = Rucksacks<'static>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        Rucksacks::parse(Alphabet::standard(), input)
+    }
+
+    fn part1(rucksacks: &Self::Input<'_>) -> u32 {
+        rucksacks.rucksack_priorities()
+    }
+
+    /// Rucksacks left over after the last whole group are ignored;
+    /// [`Rucksacks::group_priorities`] reports them instead.
+    fn part2(rucksacks: &Self::Input<'_>) -> u32 {
+        rucksacks.common_priorities(GROUP_SIZE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::Solution;
+
+    use crate::{Alphabet, Day03, GroupError, ItemSet, SharedError, SAMPLE};
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(Day03::part1(&Day03::parse(SAMPLE).unwrap()), 157);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(Day03::part2(&Day03::parse(SAMPLE).unwrap()), 70);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = Day03::parse("abcA\nab1B\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 3: '1' is not in the alphabet"
+        );
+
+        let err = Day03::parse("abc\n").unwrap_err();
+        assert_eq!(err.line, 1);
+        assert_eq!(err.column, 3);
+    }
+
+    #[test]
+    fn test_items() {
+        let alphabet = Alphabet::standard();
+        let set = alphabet.set("azAZ").unwrap();
+        assert_eq!(alphabet.items(&set).collect::<String>(), "azAZ");
+        assert_eq!(alphabet.items(&ItemSet::new(52)).count(), 0);
+    }
+
+    #[test]
+    fn test_shared_items() {
+        let rucksacks = Day03::parse(SAMPLE).unwrap();
+        let shared: Vec<char> = rucksacks
+            .rucksack_items()
+            .into_iter()
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(shared, ['p', 'L', 'P', 'v', 't', 's']);
+
+        let shared: Vec<char> = rucksacks
+            .group_items(3)
+            .unwrap()
+            .into_iter()
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(shared, ['r', 'Z']);
+    }
+
+    #[test]
+    fn test_shared_error() {
+        let alphabet = Alphabet::standard();
+        assert_eq!(
+            alphabet.shared_item(&ItemSet::new(52)),
+            Err(SharedError::None)
+        );
+
+        let err = alphabet
+            .shared_item(&alphabet.set("aB").unwrap())
+            .unwrap_err();
+        assert_eq!(err, SharedError::Multiple(vec!['a', 'B']));
+        assert_eq!(err.to_string(), "2 item types are shared: 'a', 'B'");
+
+        let rucksacks = Day03::parse("abcd\nabab\n").unwrap();
+        assert_eq!(
+            rucksacks.rucksack_items(),
+            [
+                Err(SharedError::None),
+                Err(SharedError::Multiple(vec!['a', 'b']))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_group_size() {
+        let rucksacks = Day03::parse(SAMPLE).unwrap();
+        assert_eq!(rucksacks.groups(6).unwrap().len(), 1);
+        assert_eq!(
+            rucksacks.group_items(2).unwrap()[0],
+            Err(SharedError::Multiple(vec!['f', 'r', 's', 'F', 'M']))
+        );
+
+        let err = rucksacks.groups(4).unwrap_err();
+        assert_eq!(err, GroupError::Partial { size: 4, found: 2 });
+        assert_eq!(
+            err.to_string(),
+            "expected groups of 4, but the last group only has 2 rucksacks"
+        );
+        assert_eq!(rucksacks.groups(0), Err(GroupError::ZeroSize));
+
+        assert_eq!(rucksacks.group_priorities(3), Ok(70));
+        assert_eq!(
+            rucksacks.group_priorities(5),
+            Err(GroupError::Partial { size: 5, found: 1 })
+        );
+
+        // Part 1 doesn't need whole groups, and part 2 leaves the rest out.
+        let rucksacks = Day03::parse(&format!("{}\nabab\ncdcd\n", SAMPLE.trim_end())).unwrap();
+        assert_eq!(Day03::part1(&rucksacks), 157 + 2 + 4);
+        assert_eq!(Day03::part2(&rucksacks), 70);
+    }
+}
@@ -1,66 +1,38 @@
-use itertools::Itertools;
-
-type Bits = u64;
-
-fn bits(line: &str) -> Bits {
-    line.chars()
-        .map(|c| match c {
-            'a'..='z' => c as u32 - 'a' as u32,
-            'A'..='Z' => c as u32 - 'A' as u32 + 26,
-            _ => unreachable!(),
-        })
-        .fold(0, |bits, bit| bits | 1 << bit)
-}
-
-fn part1(input: &str) -> u32 {
-    input
-        .lines()
-        .map(|line| {
-            let (a, b) = line.split_at(line.len() / 2);
-            let common = bits(a) & bits(b);
-            Bits::BITS - common.leading_zeros()
-        })
-        .sum()
+use std::error::Error;
+
+use clap::Subcommand;
+use common::Solution;
+use day03::{Day03, GROUP_SIZE};
+
+#[derive(Subcommand)]
+enum Command {
+    /// Print the item type shared by each group of elves, and the sum of
+    /// their priorities.
+    Groups {
+        /// Input file, or `-` for stdin.
+        input: Option<String>,
+
+        /// Number of elves per group.
+        #[arg(long, default_value_t = GROUP_SIZE)]
+        size: usize,
+    },
 }
 
-fn part2(input: &str) -> u32 {
-    input
-        .lines()
-        .tuples()
-        .map(|(a, b, c)| {
-            let common = bits(a) & bits(b) & bits(c);
-            Bits::BITS - common.leading_zeros()
-        })
-        .sum()
+fn groups(input: Option<&str>, size: usize) -> Result<(), Box<dyn Error>> {
+    let input = common::input::load(Day03::DAY, input)?;
+    let rucksacks = Day03::parse(&input)?;
+    for (number, item) in (1..).zip(rucksacks.group_items(size)?) {
+        match item {
+            Ok(item) => println!("Group {number}: {item}"),
+            Err(e) => println!("Group {number}: {e}"),
+        }
+    }
+    println!("Priorities: {}", rucksacks.group_priorities(size)?);
+    Ok(())
 }
 
 fn main() {
-    let input = include_str!("input.txt");
-
-    println!("Part 1 = {}", part1(input));
-    println!("Part 2 = {}", part2(input));
-}
-
-#[cfg(test)]
-mod tests {
-    use indoc::indoc;
-
-    const SAMPLE: &str = indoc! {"
-        vJrwpWtwJgWrhcsFMMfFFhFp
-        jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
-        PmmdzqPrVvPwwTWBwg
-        wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
-        ttgJtRGJQctTZtZT
-        CrZsJsPPZsGzwwsLwLmpwMDw
-    "};
-
-    #[test]
-    fn test_part1() {
-        assert_eq!(crate::part1(SAMPLE), 157);
-    }
-
-    #[test]
-    fn test_part2() {
-        assert_eq!(crate::part2(SAMPLE), 70);
-    }
+    common::run_with::<Day03, _>(|command| match command {
+        Command::Groups { input, size } => groups(input.as_deref(), size),
+    });
 }
@@ -0,0 +1,227 @@
+//! Overlap queries over every assignment in a file, rather than within each
+//! line.
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use crate::interval::Interval;
+use crate::range::{Pair, Section, SectionNumber};
+
+/// One elf's sections.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Assignment<S = Section> {
+    /// The line it was listed on, from 1.
+    pub line: usize,
+    /// Which elf of the pair, 1 or 2.
+    pub elf: u8,
+    pub sections: Interval<S>,
+}
+
+/// Assignments sorted by their first section, laid out as an implicit balanced
+/// search tree: the middle of every slice is the root of the subtree over that
+/// slice, and stores the furthest end of anything in it. Subtrees that end
+/// before, or start after, a query are skipped, so finding `k` overlaps takes
+/// `O(log n + k)`.
+#[derive(Clone, Debug, Default)]
+pub struct Index<S = Section> {
+    assignments: Vec<Assignment<S>>,
+    max_end: Vec<S>,
+}
+
+impl<S: SectionNumber> Index<S> {
+    pub fn new(mut assignments: Vec<Assignment<S>>) -> Self {
+        assignments.retain(|a| !a.sections.is_empty());
+        assignments.sort_by_key(|a| (*a.sections.start(), a.line, a.elf));
+        let mut index = Self {
+            max_end: vec![S::MIN; assignments.len()],
+            assignments,
+        };
+        index.build(0, index.assignments.len());
+        index
+    }
+
+    /// Index both assignments on every line.
+    pub fn from_pairs(pairs: &[Pair<S>]) -> Self {
+        Self::new(
+            pairs
+                .iter()
+                .enumerate()
+                .flat_map(|(i, (a, b))| {
+                    [(1, a), (2, b)].map(|(elf, sections)| Assignment {
+                        line: i + 1,
+                        elf,
+                        sections: sections.clone(),
+                    })
+                })
+                .collect(),
+        )
+    }
+
+    fn build(&mut self, lo: usize, hi: usize) -> Option<S> {
+        if lo == hi {
+            return None;
+        }
+        let mid = lo + (hi - lo) / 2;
+        let mut end = *self.assignments[mid].sections.end();
+        end = end.max(self.build(lo, mid).unwrap_or(end));
+        end = end.max(self.build(mid + 1, hi).unwrap_or(end));
+        self.max_end[mid] = end;
+        Some(end)
+    }
+
+    pub fn len(&self) -> usize {
+        self.assignments.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.assignments.is_empty()
+    }
+
+    /// Every assignment sharing a section with `range`, by first section.
+    pub fn overlapping(&self, range: &Interval<S>) -> Vec<&Assignment<S>> {
+        let mut found = vec![];
+        if !range.is_empty() {
+            self.search(0, self.len(), range, &mut found);
+        }
+        found
+    }
+
+    /// Every assignment including `section`, by first section.
+    pub fn containing(&self, section: S) -> Vec<&Assignment<S>> {
+        self.overlapping(&(section..=section))
+    }
+
+    fn search<'a>(
+        &'a self,
+        lo: usize,
+        hi: usize,
+        range: &Interval<S>,
+        found: &mut Vec<&'a Assignment<S>>,
+    ) {
+        if lo == hi {
+            return;
+        }
+        let mid = lo + (hi - lo) / 2;
+        if self.max_end[mid] < *range.start() {
+            return;
+        }
+        self.search(lo, mid, range, found);
+        let a = &self.assignments[mid];
+        if a.sections.start() > range.end() {
+            // Everything to the right starts even later.
+            return;
+        }
+        if a.sections.end() >= range.start() {
+            found.push(a);
+        }
+        self.search(mid + 1, hi, range, found);
+    }
+
+    /// Every two assignments sharing a section, including the two on one line,
+    /// found with a sweep over the sections in `O(n log n + k)`. The earlier
+    /// starting assignment comes first.
+    pub fn overlapping_pairs(&self) -> Vec<(&Assignment<S>, &Assignment<S>)> {
+        let mut pairs = vec![];
+        // Assignments the sweep is inside of, soonest to end first.
+        let mut active: BinaryHeap<Reverse<(S, usize)>> = BinaryHeap::new();
+        for (i, a) in self.assignments.iter().enumerate() {
+            while active
+                .peek()
+                .is_some_and(|Reverse((end, _))| end < a.sections.start())
+            {
+                active.pop();
+            }
+            pairs.extend(
+                active
+                    .iter()
+                    .map(|&Reverse((_, j))| (&self.assignments[j], a)),
+            );
+            active.push(Reverse((*a.sections.end(), i)));
+        }
+        pairs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::Solution;
+    use proptest::prelude::*;
+
+    use super::{Assignment, Index};
+    use crate::range::Section;
+    use crate::{Day04, SAMPLE};
+
+    fn ids(assignments: Vec<&Assignment>) -> Vec<(usize, u8)> {
+        let mut ids: Vec<_> = assignments.iter().map(|a| (a.line, a.elf)).collect();
+        ids.sort_unstable();
+        ids
+    }
+
+    #[test]
+    fn test_sample() {
+        let index = Index::from_pairs(&Day04::parse(SAMPLE).unwrap());
+        assert_eq!(index.len(), 12);
+        assert_eq!(ids(index.containing(9)), [(3, 2)]);
+        assert_eq!(ids(index.containing(1)), []);
+        assert_eq!(
+            ids(index.overlapping(&(8..=20))),
+            [(1, 2), (3, 2), (4, 1), (6, 2)]
+        );
+
+        // Only the pairs on lines 1 and 2 don't overlap each other.
+        let pairs = index.overlapping_pairs();
+        for line in 3..=6 {
+            assert!(pairs.iter().any(|(a, b)| a.line == line && b.line == line));
+        }
+        assert!(!pairs.iter().any(|(a, b)| a.line == b.line && a.line <= 2));
+    }
+
+    fn assignments() -> impl Strategy<Value = Vec<Assignment>> {
+        prop::collection::vec((0..60 as Section, 0..60 as Section), 0..40).prop_map(|ranges| {
+            ranges
+                .into_iter()
+                .enumerate()
+                .map(|(i, (a, b))| Assignment {
+                    line: i + 1,
+                    elf: 1,
+                    sections: a.min(b)..=a.max(b),
+                })
+                .collect()
+        })
+    }
+
+    fn overlap(a: &Assignment, b: &Assignment) -> bool {
+        a.sections.start() <= b.sections.end() && b.sections.start() <= a.sections.end()
+    }
+
+    proptest! {
+        #[test]
+        fn test_overlapping(assignments in assignments(), a in 0..60 as Section, b in 0..60 as Section) {
+            let index = Index::new(assignments.clone());
+            let query = Assignment { line: 0, elf: 0, sections: a.min(b)..=a.max(b) };
+            let expected: Vec<&Assignment> = assignments.iter().filter(|x| overlap(x, &query)).collect();
+            prop_assert_eq!(ids(index.overlapping(&query.sections)), ids(expected));
+        }
+
+        #[test]
+        fn test_overlapping_pairs(assignments in assignments()) {
+            let index = Index::new(assignments.clone());
+            let mut pairs: Vec<(usize, usize)> = index
+                .overlapping_pairs()
+                .into_iter()
+                .map(|(a, b)| (a.line.min(b.line), a.line.max(b.line)))
+                .collect();
+            pairs.sort_unstable();
+
+            let mut expected = vec![];
+            for (i, a) in assignments.iter().enumerate() {
+                for b in &assignments[i + 1..] {
+                    if overlap(a, b) {
+                        expected.push((a.line, b.line));
+                    }
+                }
+            }
+            prop_assert_eq!(pairs, expected);
+        }
+    }
+}
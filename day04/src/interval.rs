@@ -0,0 +1,200 @@
+//! Set operations on section assignments. Sections are whole numbers, so
+//! ranges that touch, like `2-3` and `4-5`, cover one unbroken run.
+
+use std::ops::RangeInclusive;
+
+use crate::range::{Section, SectionNumber};
+
+pub type Interval<S = Section> = RangeInclusive<S>;
+
+/// Whether `a` and `b` overlap or are next to each other.
+fn touches<S: SectionNumber>(a: &Interval<S>, b: &Interval<S>) -> bool {
+    let after = |s: &S| s.checked_next().unwrap_or(S::MAX);
+    *a.start() <= after(b.end()) && *b.start() <= after(a.end())
+}
+
+/// The sections in both `a` and `b`.
+pub fn intersection<S: SectionNumber>(a: &Interval<S>, b: &Interval<S>) -> Option<Interval<S>> {
+    let range = *a.start().max(b.start())..=*a.end().min(b.end());
+    (!range.is_empty()).then_some(range)
+}
+
+/// The sections in either `a` or `b`, if they form one range. Use [`merge`]
+/// for ranges with gaps between them.
+pub fn union<S: SectionNumber>(a: &Interval<S>, b: &Interval<S>) -> Option<Interval<S>> {
+    match (a.is_empty(), b.is_empty()) {
+        (true, _) => Some(b.clone()),
+        (_, true) => Some(a.clone()),
+        _ if touches(a, b) => Some(*a.start().min(b.start())..=*a.end().max(b.end())),
+        _ => None,
+    }
+}
+
+/// The sections in `a` but not in `b`, which is up to two ranges.
+pub fn difference<S: SectionNumber>(a: &Interval<S>, b: &Interval<S>) -> Vec<Interval<S>> {
+    let Some(common) = intersection(a, b) else {
+        return if a.is_empty() {
+            vec![]
+        } else {
+            vec![a.clone()]
+        };
+    };
+    let mut pieces = vec![];
+    if a.start() < common.start() {
+        pieces.push(*a.start()..=common.start().checked_prev().unwrap());
+    }
+    if common.end() < a.end() {
+        pieces.push(common.end().checked_next().unwrap()..=*a.end());
+    }
+    pieces
+}
+
+/// The sections in any of `ranges`, as the fewest sorted, disjoint ranges.
+pub fn merge<S: SectionNumber>(ranges: impl IntoIterator<Item = Interval<S>>) -> Vec<Interval<S>> {
+    let mut ranges: Vec<Interval<S>> = ranges.into_iter().filter(|r| !r.is_empty()).collect();
+    ranges.sort_unstable_by_key(|r| *r.start());
+
+    let mut merged: Vec<Interval<S>> = Vec::with_capacity(ranges.len());
+    for range in ranges {
+        match merged.last_mut() {
+            Some(last) if touches(last, &range) => {
+                *last = *last.start()..=*last.end().max(range.end());
+            }
+            _ => merged.push(range),
+        }
+    }
+    merged
+}
+
+/// How many sections are in any of `ranges`. This can be one more than
+/// `u64::MAX`, for an unbounded range starting at 0.
+pub fn coverage<S: SectionNumber>(ranges: impl IntoIterator<Item = Interval<S>>) -> u128 {
+    merge(ranges)
+        .iter()
+        .map(|r| (*r.end()).into() - (*r.start()).into() + 1)
+        .sum()
+}
+
+/// The sections in `within` that none of `ranges` cover.
+pub fn gaps<S: SectionNumber>(
+    ranges: impl IntoIterator<Item = Interval<S>>,
+    within: &Interval<S>,
+) -> Vec<Interval<S>> {
+    let mut gaps = vec![within.clone()];
+    for range in merge(ranges) {
+        let Some(last) = gaps.pop() else { break };
+        gaps.extend(difference(&last, &range));
+    }
+    gaps
+}
+
+/// The sections covered by two or more of `ranges`.
+pub fn overlaps<S: SectionNumber>(
+    ranges: impl IntoIterator<Item = Interval<S>>,
+) -> Vec<Interval<S>> {
+    let mut ranges: Vec<Interval<S>> = ranges.into_iter().filter(|r| !r.is_empty()).collect();
+    ranges.sort_unstable_by_key(|r| *r.start());
+
+    // Every section from a range's start up to the furthest end of the ranges
+    // before it is covered at least twice.
+    let mut shared = vec![];
+    let mut reach: Option<S> = None;
+    for range in ranges {
+        if let Some(end) = reach {
+            shared.extend(intersection(&range, &(*range.start()..=end)));
+        }
+        reach = Some(reach.map_or(*range.end(), |end| end.max(*range.end())));
+    }
+    merge(shared)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use common::Solution;
+    use proptest::prelude::*;
+
+    use super::{coverage, difference, gaps, intersection, merge, overlaps, union, Interval};
+    use crate::range::Section;
+    use crate::{Day04, SAMPLE};
+
+    #[test]
+    fn test_pairs() {
+        let r = |a: Section, b: Section| a..=b;
+        assert_eq!(intersection(&r(2, 6), &r(4, 8)), Some(4..=6));
+        assert_eq!(intersection(&r(2, 3), &r(4, 5)), None);
+        assert_eq!(union(&r(2, 3), &r(4, 5)), Some(2..=5));
+        assert_eq!(union(&r(2, 3), &r(5, 5)), None);
+        assert_eq!(difference(&r(2, 8), &r(3, 7)), [2..=2, 8..=8]);
+        assert_eq!(difference(&r(3, 7), &r(2, 8)), []);
+        assert_eq!(difference(&r(2, 4), &r(6, 8)), [2..=4]);
+        assert_eq!(difference(&(0..=u8::MAX), &(0..=254)), [255..=255]);
+        assert_eq!(union(&(0..=u8::MAX), &(4..=u8::MAX)), Some(0..=255));
+        assert_eq!(coverage([r(0, u64::MAX)]), 1 << 64);
+    }
+
+    #[test]
+    fn test_sample() {
+        let pairs = Day04::parse(SAMPLE).unwrap();
+        let ranges = || pairs.iter().flat_map(|(a, b)| [a.clone(), b.clone()]);
+        assert_eq!(merge(ranges()), [2..=9]);
+        assert_eq!(coverage(ranges()), 8);
+        assert_eq!(gaps(ranges(), &(1..=10)), [1..=1, 10..=10]);
+        assert_eq!(overlaps(ranges()), [2..=8]);
+    }
+
+    fn range() -> impl Strategy<Value = Interval> {
+        (0..40 as Section, 0..40 as Section).prop_map(|(a, b)| a.min(b)..=a.max(b))
+    }
+
+    /// How many of `ranges` cover each section.
+    fn counts(ranges: &[Interval]) -> HashMap<Section, usize> {
+        let mut counts = HashMap::new();
+        for s in ranges.iter().flat_map(|r| r.clone()) {
+            *counts.entry(s).or_default() += 1;
+        }
+        counts
+    }
+
+    fn sections(ranges: &[Interval]) -> Vec<Section> {
+        ranges.iter().flat_map(|r| r.clone()).collect()
+    }
+
+    proptest! {
+        #[test]
+        fn test_merge(ranges in prop::collection::vec(range(), 0..10)) {
+            let merged = merge(ranges.clone());
+            let mut expected: Vec<Section> = counts(&ranges).into_keys().collect();
+            expected.sort_unstable();
+            prop_assert_eq!(sections(&merged), expected.clone());
+            prop_assert_eq!(coverage(ranges.clone()), expected.len() as u128);
+            // Merged ranges don't touch.
+            for w in merged.windows(2) {
+                prop_assert!(w[0].end() + 1 < *w[1].start());
+            }
+        }
+
+        #[test]
+        fn test_gaps_and_overlaps(ranges in prop::collection::vec(range(), 0..10)) {
+            let counts = counts(&ranges);
+            let within = 5..=30;
+            let expected: Vec<Section> = within.clone().filter(|s| !counts.contains_key(s)).collect();
+            prop_assert_eq!(sections(&gaps(ranges.clone(), &within)), expected);
+
+            let mut expected: Vec<Section> = counts
+                .into_iter()
+                .filter(|&(_, n)| n >= 2)
+                .map(|(s, _)| s)
+                .collect();
+            expected.sort_unstable();
+            prop_assert_eq!(sections(&overlaps(ranges)), expected);
+        }
+
+        #[test]
+        fn test_difference(a in range(), b in range()) {
+            let expected: Vec<Section> = a.clone().filter(|s| !b.contains(s)).collect();
+            prop_assert_eq!(sections(&difference(&a, &b)), expected);
+        }
+    }
+}
@@ -0,0 +1,270 @@
+use common::{parse_lines, ParseError, Solution};
+use indoc::indoc;
+use range::Pair;
+
+pub mod index;
+pub mod interval;
+
+pub mod range {
+    use std::fmt::{Debug, Display};
+    use std::hash::Hash;
+    use std::ops::RangeInclusive;
+    use std::str::FromStr;
+
+    use common::ParseError;
+    use nom::branch::alt;
+    use nom::bytes::complete::tag;
+    use nom::character::complete::digit1;
+    use nom::combinator::{all_consuming, cut, map, map_res, opt, verify};
+    use nom::error::ErrorKind;
+    use nom::sequence::{pair, preceded, separated_pair};
+    use nom::{Finish, IResult};
+
+    /// An unsigned integer type that sections can be numbered with.
+    pub trait SectionNumber: Copy + Ord + Hash + Debug + Display + FromStr + Into<u128> {
+        const MIN: Self;
+        const MAX: Self;
+
+        fn checked_next(self) -> Option<Self>;
+        fn checked_prev(self) -> Option<Self>;
+    }
+
+    macro_rules! section_number {
+        ($($t:ty),*) => {
+            $(impl SectionNumber for $t {
+                const MIN: Self = <$t>::MIN;
+                const MAX: Self = <$t>::MAX;
+
+                fn checked_next(self) -> Option<Self> {
+                    self.checked_add(1)
+                }
+
+                fn checked_prev(self) -> Option<Self> {
+                    self.checked_sub(1)
+                }
+            })*
+        };
+    }
+
+    section_number!(u8, u16, u32, u64);
+
+    pub type Section = u64;
+    pub type Pair<S = Section> = (RangeInclusive<S>, RangeInclusive<S>);
+
+    /// Parse two comma separated ranges, each of which is either inclusive
+    /// (`2-4`), half-open (`2..5`) or unbounded (`2-`, up to `S::MAX`).
+    pub fn from_string<S: SectionNumber>(s: &str) -> Result<Pair<S>, ParseError> {
+        match all_consuming(parse)(s).finish() {
+            Ok((_, pair)) => Ok(pair),
+            Err(e) => {
+                let message = match e.code {
+                    ErrorKind::MapRes => format!("section number must be at most {}", S::MAX),
+                    ErrorKind::Verify => "half-open range must not be empty".to_owned(),
+                    _ => "expected two ranges like \"2-4,6-8\"".to_owned(),
+                };
+                Err(ParseError::column(s.len() - e.input.len() + 1, message))
+            }
+        }
+    }
+
+    fn parse<S: SectionNumber>(s: &str) -> IResult<&str, Pair<S>> {
+        separated_pair(parse_range, tag(","), parse_range)(s)
+    }
+
+    fn parse_number<S: SectionNumber>(s: &str) -> IResult<&str, S> {
+        map_res(digit1, str::parse)(s)
+    }
+
+    /// How a range ends.
+    enum End<S> {
+        Exclusive(S),
+        Inclusive(S),
+        Unbounded,
+    }
+
+    fn parse_end<S: SectionNumber>(s: &str) -> IResult<&str, End<S>> {
+        // Cut after the separator so that errors in the number, like it not
+        // fitting, aren't lost by trying the other kind of range instead.
+        alt((
+            map(preceded(tag(".."), cut(parse_number)), End::Exclusive),
+            map(
+                preceded(
+                    tag("-"),
+                    cut(map_res(opt(digit1), |d: Option<&str>| {
+                        d.map(str::parse).transpose()
+                    })),
+                ),
+                |b| b.map_or(End::Unbounded, End::Inclusive),
+            ),
+        ))(s)
+    }
+
+    fn parse_range<S: SectionNumber>(s: &str) -> IResult<&str, RangeInclusive<S>> {
+        map(
+            verify(
+                pair(parse_number, parse_end::<S>),
+                |(a, end)| !matches!(end, End::Exclusive(b) if b <= a),
+            ),
+            |(a, end)| match end {
+                End::Exclusive(b) => a..=b.checked_prev().unwrap(),
+                End::Inclusive(b) => a..=b,
+                End::Unbounded => a..=S::MAX,
+            },
+        )(s)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::{from_string, Pair};
+
+        #[test]
+        fn test_parse_line() {
+            assert_eq!(from_string::<u8>("2-4,6-8"), Ok(((2..=4), (6..=8))));
+            assert_eq!(from_string::<u64>("2..5,6-"), Ok(((2..=4), (6..=u64::MAX))));
+            assert_eq!(
+                from_string::<u64>("300-70000,18446744073709551615-"),
+                Ok(((300..=70000), (u64::MAX..=u64::MAX)))
+            );
+        }
+
+        #[test]
+        fn test_parse_error() {
+            let err = from_string::<u64>("2-4,6_8").unwrap_err();
+            assert_eq!(err.column, 6);
+
+            let err = from_string::<u64>("2-4,6-8 ").unwrap_err();
+            assert_eq!(err.column, 8);
+
+            let err = from_string::<u8>("2-4,6-256").unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                "line 1, column 7: section number must be at most 255"
+            );
+
+            let err = from_string::<u64>("2-18446744073709551616,1-2").unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                "line 1, column 3: section number must be at most 18446744073709551615"
+            );
+
+            let err: Result<Pair<u16>, _> = from_string("4..4,1-2");
+            assert_eq!(
+                err.unwrap_err().to_string(),
+                "line 1, column 1: half-open range must not be empty"
+            );
+        }
+    }
+}
+
+pub trait RangeInclusiveExt {
+    fn contains_range(&self, other: &Self) -> bool;
+    fn overlaps(&self, other: &Self) -> bool;
+}
+
+impl<T> RangeInclusiveExt for std::ops::RangeInclusive<T>
+where
+    T: PartialOrd,
+{
+    /// Check if range fully contains other.
+    fn contains_range(&self, other: &Self) -> bool {
+        self.contains(other.start()) && self.contains(other.end())
+    }
+
+    /// Check if range has any overlap with other.
+    fn overlaps(&self, other: &Self) -> bool {
+        self.contains(other.start()) || self.contains(other.end())
+    }
+}
+
+const SAMPLE: &str = indoc! {"
+    2-4,6-8
+    2-3,4-5
+    5-7,7-9
+    2-8,3-7
+    6-6,4-6
+    2-6,4-8
+"};
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+    const SAMPLE: &'static str = SAMPLE;
+
+    type Input<'a> = Vec<Pair>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_lines(input, range::from_string)
+    }
+
+    fn part1(pairs: &Self::Input<'_>) -> usize {
+        pairs
+            .iter()
+            .filter(|(a, b)| a.contains_range(b) || b.contains_range(a))
+            .count()
+    }
+
+    fn part2(pairs: &Self::Input<'_>) -> usize {
+        pairs
+            .iter()
+            .filter(|(a, b)| a.overlaps(b) || b.overlaps(a))
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ops::RangeInclusive;
+
+    use common::Solution;
+    use proptest::prelude::*;
+
+    use crate::range::Section;
+    use crate::{Day04, RangeInclusiveExt, SAMPLE};
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(Day04::part1(&Day04::parse(SAMPLE).unwrap()), 2);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(Day04::part2(&Day04::parse(SAMPLE).unwrap()), 4);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = Day04::parse("2-4,6-8\n300-400,5..5\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 9: half-open range must not be empty"
+        );
+    }
+
+    fn range() -> impl Strategy<Value = RangeInclusive<Section>> {
+        (any::<Section>(), any::<Section>()).prop_map(|(a, b)| a.min(b)..=a.max(b))
+    }
+
+    proptest! {
+        #[test]
+        fn test_contains_range_implies_overlaps(a in range(), b in range()) {
+            if a.contains_range(&b) {
+                prop_assert!(a.overlaps(&b));
+            }
+        }
+
+        #[test]
+        fn test_overlaps_either_way(a in range(), b in range()) {
+            let expected = a.start().max(b.start()) <= a.end().min(b.end());
+            prop_assert_eq!(a.overlaps(&b) || b.overlaps(&a), expected);
+        }
+
+        #[test]
+        fn test_parse_roundtrip(a in range(), b in range()) {
+            let line = format!("{}-{},{}-{}", a.start(), a.end(), b.start(), b.end());
+            prop_assert_eq!(crate::range::from_string(&line), Ok((a, b)));
+        }
+    }
+}
@@ -0,0 +1,346 @@
+use std::fmt::Write;
+
+use crate::Procedure;
+
+/// How a crane moves several crates at once.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Model {
+    /// One crate at a time, which reverses their order (part 1).
+    #[default]
+    CrateMover9000,
+    /// All of them together, keeping their order (part 2).
+    CrateMover9001,
+}
+
+impl Model {
+    /// Carry out `p`, which [`validate`] has checked.
+    fn apply(self, stacks: &mut [Vec<char>], p: &Procedure) {
+        match self {
+            Model::CrateMover9000 => {
+                for _ in 0..p.count {
+                    let v = stacks[p.from].pop().unwrap();
+                    stacks[p.to].push(v);
+                }
+            }
+            Model::CrateMover9001 => {
+                let n = stacks[p.from].len();
+                let mut v = stacks[p.from].split_off(n - p.count);
+                stacks[p.to].append(&mut v);
+            }
+        }
+    }
+}
+
+/// A procedure that can't be carried out.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MoveError {
+    NoSuchStack {
+        /// The number of the procedure, from 1.
+        step: usize,
+        /// The stack number, from 1 like in the input.
+        stack: usize,
+        stacks: usize,
+    },
+    NotEnoughCrates {
+        step: usize,
+        stack: usize,
+        count: usize,
+        available: usize,
+    },
+}
+
+impl MoveError {
+    /// The number of the procedure, from 1.
+    pub fn step(&self) -> usize {
+        match self {
+            MoveError::NoSuchStack { step, .. } | MoveError::NotEnoughCrates { step, .. } => *step,
+        }
+    }
+}
+
+impl std::fmt::Display for MoveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MoveError::NoSuchStack {
+                step,
+                stack,
+                stacks,
+            } => write!(
+                f,
+                "move {step}: there is no stack {stack}, only {stacks} stacks"
+            ),
+            MoveError::NotEnoughCrates {
+                step,
+                stack,
+                count,
+                available,
+            } => write!(
+                f,
+                "move {step}: stack {stack} has {available} crates, {} short of moving {count}",
+                count - available
+            ),
+        }
+    }
+}
+
+impl std::error::Error for MoveError {}
+
+/// Check that every procedure can be carried out in turn, which only depends
+/// on the number of crates in each stack, not on the crane model.
+pub fn validate(stacks: &[Vec<char>], procedures: &[Procedure]) -> Result<(), MoveError> {
+    let mut sizes: Vec<usize> = stacks.iter().map(Vec::len).collect();
+    for (i, p) in procedures.iter().enumerate() {
+        let step = i + 1;
+        for stack in [p.from, p.to] {
+            if stack >= sizes.len() {
+                return Err(MoveError::NoSuchStack {
+                    step,
+                    stack: stack + 1,
+                    stacks: sizes.len(),
+                });
+            }
+        }
+        if sizes[p.from] < p.count {
+            return Err(MoveError::NotEnoughCrates {
+                step,
+                stack: p.from + 1,
+                count: p.count,
+                available: sizes[p.from],
+            });
+        }
+        sizes[p.from] -= p.count;
+        sizes[p.to] += p.count;
+    }
+    Ok(())
+}
+
+/// Draw stacks like the puzzle input, with the stack numbers underneath.
+pub fn draw(stacks: &[Vec<char>]) -> String {
+    let mut output = String::new();
+    let height = stacks.iter().map(Vec::len).max().unwrap_or(0);
+    for row in (0..height).rev() {
+        let layer: Vec<String> = stacks
+            .iter()
+            .map(|s| s.get(row).map_or("   ".to_owned(), |c| format!("[{c}]")))
+            .collect();
+        writeln!(output, "{}", layer.join(" ")).unwrap();
+    }
+    let numbers: Vec<String> = (1..=stacks.len()).map(|i| format!(" {i} ")).collect();
+    writeln!(output, "{}", numbers.join(" ")).unwrap();
+    output
+}
+
+/// Carries out a rearrangement one procedure at a time, and can go back and
+/// forth between the steps.
+#[derive(Clone, Debug)]
+pub struct Crane<'a> {
+    model: Model,
+    stacks: Vec<Vec<char>>,
+    procedures: &'a [Procedure],
+    /// How many procedures have been carried out.
+    step: usize,
+}
+
+impl<'a> Crane<'a> {
+    /// Fails if any of the procedures can't be carried out, so that stepping
+    /// through them never can.
+    pub fn new(
+        model: Model,
+        stacks: &[Vec<char>],
+        procedures: &'a [Procedure],
+    ) -> Result<Self, MoveError> {
+        validate(stacks, procedures)?;
+        Ok(Self {
+            model,
+            stacks: stacks.to_vec(),
+            procedures,
+            step: 0,
+        })
+    }
+
+    pub fn stacks(&self) -> &[Vec<char>] {
+        &self.stacks
+    }
+
+    pub fn step(&self) -> usize {
+        self.step
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.step == self.procedures.len()
+    }
+
+    /// The crate on top of each stack. Empty stacks are left out.
+    pub fn tops(&self) -> String {
+        self.stacks.iter().filter_map(|v| v.last()).collect()
+    }
+
+    /// Carry out the next procedure, if there is one.
+    pub fn forward(&mut self) -> Option<&'a Procedure> {
+        let p = self.procedures.get(self.step)?;
+        self.model.apply(&mut self.stacks, p);
+        self.step += 1;
+        Some(p)
+    }
+
+    /// Undo the last procedure, if any. Moving the crates straight back with
+    /// the same model puts them in their old order either way.
+    pub fn back(&mut self) -> Option<&'a Procedure> {
+        let p = self.procedures.get(self.step.checked_sub(1)?)?;
+        let undo = Procedure {
+            count: p.count,
+            from: p.to,
+            to: p.from,
+        };
+        self.model.apply(&mut self.stacks, &undo);
+        self.step -= 1;
+        Some(p)
+    }
+
+    /// Replay or rewind to after `step` procedures, or as far as they go.
+    pub fn seek(&mut self, step: usize) {
+        let step = step.min(self.procedures.len());
+        while self.step < step {
+            self.forward();
+        }
+        while self.step > step {
+            self.back();
+        }
+    }
+
+    /// Carry out the remaining procedures.
+    pub fn run(&mut self) {
+        self.seek(self.procedures.len());
+    }
+
+    /// A drawing of the stacks before the next procedure and after each of the
+    /// remaining ones, headed by the procedure that led there.
+    pub fn trace(&mut self) -> String {
+        let mut output = format!("Step {}:\n{}", self.step, draw(&self.stacks));
+        while let Some(p) = self.forward() {
+            write!(
+                output,
+                "\nStep {}: move {} from {} to {}\n{}",
+                self.step,
+                p.count,
+                p.from + 1,
+                p.to + 1,
+                draw(&self.stacks)
+            )
+            .unwrap();
+        }
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use super::{draw, validate, Crane, Model, MoveError};
+    use crate::{parse_input, Procedure, SAMPLE};
+
+    #[test]
+    fn test_draw() {
+        let (stacks, _) = parse_input(SAMPLE).unwrap();
+        assert_eq!(
+            draw(&stacks),
+            SAMPLE.split_once("\n\n").unwrap().0.to_owned() + "\n"
+        );
+    }
+
+    #[test]
+    fn test_seek() {
+        let (stacks, procedures) = parse_input(SAMPLE).unwrap();
+        for model in [Model::CrateMover9000, Model::CrateMover9001] {
+            let mut crane = Crane::new(model, &stacks, &procedures).unwrap();
+            let mut states = vec![crane.stacks().to_vec()];
+            while crane.forward().is_some() {
+                states.push(crane.stacks().to_vec());
+            }
+            assert!(crane.is_done());
+            assert_eq!(crane.forward(), None);
+
+            for step in [2, 0, 4, 1, 3, 100] {
+                crane.seek(step);
+                let step = step.min(procedures.len());
+                assert_eq!(crane.step(), step);
+                assert_eq!(crane.stacks(), states[step]);
+            }
+        }
+
+        let mut crane = Crane::new(Model::CrateMover9001, &stacks, &procedures).unwrap();
+        assert_eq!(crane.back(), None);
+        crane.run();
+        assert_eq!(crane.tops(), "MCD");
+        assert_eq!(crane.back(), procedures.last());
+    }
+
+    #[test]
+    fn test_trace() {
+        let (stacks, procedures) = parse_input(SAMPLE).unwrap();
+        let mut crane = Crane::new(Model::CrateMover9000, &stacks, &procedures[..1]).unwrap();
+        let trace = crane.trace();
+        // Ignore the padding of the drawings.
+        assert_eq!(
+            trace.lines().map(str::trim_end).collect::<Vec<_>>(),
+            indoc! {"
+                Step 0:
+                    [D]
+                [N] [C]
+                [Z] [M] [P]
+                 1   2   3
+
+                Step 1: move 1 from 2 to 1
+                [D]
+                [N] [C]
+                [Z] [M] [P]
+                 1   2   3
+            "}
+            .lines()
+            .collect::<Vec<_>>()
+        );
+        assert!(crane.is_done());
+    }
+
+    #[test]
+    fn test_validate() {
+        let (stacks, mut procedures) = parse_input(SAMPLE).unwrap();
+        assert_eq!(validate(&stacks, &procedures), Ok(()));
+
+        // Stack 1 is down to 2 crates after the third move.
+        procedures[3].count = 3;
+        let err = validate(&stacks, &procedures).unwrap_err();
+        assert_eq!(
+            err,
+            MoveError::NotEnoughCrates {
+                step: 4,
+                stack: 1,
+                count: 3,
+                available: 2
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "move 4: stack 1 has 2 crates, 1 short of moving 3"
+        );
+
+        procedures[0].to = 3;
+        let err = Crane::new(Model::CrateMover9001, &stacks, &procedures).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "move 1: there is no stack 4, only 3 stacks"
+        );
+
+        let empty = [Procedure {
+            count: 2,
+            from: 0,
+            to: 1,
+        }];
+        assert!(validate(&[vec!['A'], vec![]], &empty).is_err());
+        let mut crane =
+            Crane::new(Model::CrateMover9000, &[vec!['A', 'B'], vec![]], &empty).unwrap();
+        crane.run();
+        assert_eq!(crane.tops(), "A");
+    }
+}
@@ -0,0 +1,290 @@
+use common::{ParseError, Solution};
+use crane::{Crane, Model};
+use indoc::indoc;
+
+pub mod crane;
+
+peg::parser! {
+    grammar parser() for str {
+        rule number() -> usize
+            = quiet!{ n:$(['0'..='9']+) {? n.parse().or(Err("number")) } } / expected!("number")
+
+        rule stack() -> usize
+            = n:number() {? n.checked_sub(1).ok_or("stack number of at least 1") }
+
+        rule char() -> char
+            = c:['A'..='Z'] { c }
+
+        rule box() -> Option<char>
+            = "[" c:(char()) "]" { Some(c) }
+            / "   " { None }
+
+        rule layer() -> Vec<Option<char>>
+            = v:(box() ++ " ") "\n"? { v }
+
+        pub rule stacks() -> Vec<Vec<char>>
+            = v:(layer()*) [_]* { transpose(v) }
+
+        rule procedure() -> Procedure
+            = "move " a:(number()) " from " b:(stack()) " to " c:(stack()) {
+                Procedure { count: a, from: b, to: c }
+            }
+
+        pub rule procedures() -> Vec<Procedure>
+            = v:(procedure() ** "\n") "\n"? { v }
+    }
+}
+
+fn transpose<T>(mut v: Vec<Vec<Option<T>>>) -> Vec<Vec<T>> {
+    for inner in &mut v {
+        inner.reverse();
+    }
+    let width = v.iter().map(Vec::len).max().unwrap_or(0);
+    (0..width)
+        .map(|_| {
+            v.iter_mut()
+                .filter_map(|inner| inner.pop().flatten())
+                .rev()
+                .collect()
+        })
+        .collect()
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Procedure {
+    pub count: usize,
+    pub from: usize,
+    pub to: usize,
+}
+
+fn parse_error(e: peg::error::ParseError<peg::str::LineCol>) -> ParseError {
+    ParseError::new(
+        e.location.line,
+        e.location.column,
+        format!("expected {}", e.expected),
+    )
+}
+
+pub fn parse_input(input: &str) -> Result<(Vec<Vec<char>>, Vec<Procedure>), ParseError> {
+    let (stacks, procedures) = input.split_once("\n\n").ok_or_else(|| {
+        ParseError::new(
+            input.lines().count() + 1,
+            1,
+            "expected a blank line between the stacks and the procedures",
+        )
+    })?;
+    let stacks = parser::stacks(stacks).map_err(parse_error)?;
+    if stacks.is_empty() {
+        return Err(ParseError::new(1, 1, "expected at least one stack"));
+    }
+    let offset = input[..input.len() - procedures.len()].lines().count();
+    let procedures = parser::procedures(procedures).map_err(|e| parse_error(e).offset(offset))?;
+    crane::validate(&stacks, &procedures)
+        .map_err(|e| ParseError::new(offset + e.step(), 1, e.to_string()))?;
+    Ok((stacks, procedures))
+}
+
+// Trailing whitespace is intentional.
+const SAMPLE: &str = indoc! {"
+        [D]    
+    [N] [C]    
+    [Z] [M] [P]
+     1   2   3 
+
+    move 1 from 2 to 1
+    move 3 from 1 to 3
+    move 2 from 2 to 1
+    move 1 from 1 to 2
+"};
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+    const SAMPLE: &'static str = SAMPLE;
+
+    type Input<'a> = (Vec<Vec<char>>, Vec<Procedure>);
+    type Answer1 = String;
+    type Answer2 = String;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1((stacks, procedures): &Self::Input<'_>) -> String {
+        let mut crane = Crane::new(Model::CrateMover9000, stacks, procedures)
+            .expect("parse validates the procedures");
+        crane.run();
+        crane.tops()
+    }
+
+    fn part2((stacks, procedures): &Self::Input<'_>) -> String {
+        let mut crane = Crane::new(Model::CrateMover9001, stacks, procedures)
+            .expect("parse validates the procedures");
+        crane.run();
+        crane.tops()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fmt::Write;
+
+    use common::Solution;
+    use proptest::prelude::*;
+    use proptest::sample::Index;
+
+    use crate::crane::draw;
+    use crate::{parse_input, Day05, Procedure, SAMPLE};
+
+    #[test]
+    fn test_parse_stack() {
+        let stacks = crate::parser::stacks("[N] [C]     [A]").unwrap();
+        assert_eq!(stacks, [vec!['N'], vec!['C'], vec![], vec!['A']]);
+
+        let stacks = crate::parser::stacks("[N]     [A]\n[D] [E] [F]\n 1   2   3 ").unwrap();
+        assert_eq!(stacks, [vec!['D', 'N'], vec!['E'], vec!['F', 'A']]);
+    }
+
+    #[test]
+    fn test_parse_input() {
+        let (stacks, procedures) = parse_input(SAMPLE).unwrap();
+        assert_eq!(stacks, [vec!['Z', 'N'], vec!['M', 'C', 'D'], vec!['P'],]);
+        assert_eq!(
+            procedures,
+            [
+                Procedure {
+                    count: 1,
+                    from: 2 - 1,
+                    to: 1 - 1
+                },
+                Procedure {
+                    count: 3,
+                    from: 1 - 1,
+                    to: 3 - 1
+                },
+                Procedure {
+                    count: 2,
+                    from: 2 - 1,
+                    to: 1 - 1
+                },
+                Procedure {
+                    count: 1,
+                    from: 1 - 1,
+                    to: 2 - 1
+                },
+            ]
+        )
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(
+            Day05::part1(&Day05::parse(SAMPLE).unwrap()),
+            "CMZ".to_string()
+        );
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(
+            Day05::part2(&Day05::parse(SAMPLE).unwrap()),
+            "MCD".to_string()
+        );
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = parse_input(&SAMPLE.replace("move 2 from 2", "move 2 from 0")).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 8, column 14: expected stack number of at least 1"
+        );
+
+        let err = parse_input(&SAMPLE.replace("move 3", "mvoe 3")).unwrap_err();
+        assert_eq!(err.line, 7);
+        assert_eq!(err.column, 1);
+
+        let err = parse_input("[A]\n 1 \n").unwrap_err();
+        assert_eq!(err.line, 3);
+
+        let err = parse_input(&SAMPLE.replace("move 3 from 1", "move 4 from 1")).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 7, column 1: move 2: stack 1 has 3 crates, 1 short of moving 4"
+        );
+
+        let err = parse_input(&SAMPLE.replace("to 2", "to 9")).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 9, column 1: move 4: there is no stack 9, only 3 stacks"
+        );
+
+        // Every procedure has a line of its own.
+        let err = parse_input(&SAMPLE.replace("to 1\nmove", "to 1 move")).unwrap_err();
+        assert_eq!(err.line, 6);
+        assert_eq!(err.column, 19);
+    }
+
+    /// Turn random picks into moves that always leave at least one crate behind, so
+    /// every stack keeps a crate on top for part 1.
+    fn procedures(stacks: &[Vec<char>], picks: &[(Index, Index, Index)]) -> Vec<Procedure> {
+        let mut sizes: Vec<usize> = stacks.iter().map(Vec::len).collect();
+        picks
+            .iter()
+            .map_while(|(from, to, count)| {
+                let movable: Vec<usize> = (0..sizes.len()).filter(|&i| sizes[i] > 1).collect();
+                if movable.is_empty() {
+                    return None;
+                }
+                let from = *from.get(&movable);
+                let to = (from + 1 + to.index(sizes.len() - 1)) % sizes.len();
+                let count = 1 + count.index(sizes[from] - 1);
+                sizes[from] -= count;
+                sizes[to] += count;
+                Some(Procedure { count, from, to })
+            })
+            .collect()
+    }
+
+    /// Draw the stacks and procedures like the puzzle input.
+    fn render(stacks: &[Vec<char>], procedures: &[Procedure]) -> String {
+        let mut output = draw(stacks);
+        output.push('\n');
+        for p in procedures {
+            writeln!(
+                output,
+                "move {} from {} to {}",
+                p.count,
+                p.from + 1,
+                p.to + 1
+            )
+            .unwrap();
+        }
+        output
+    }
+
+    /// Move crates as a whole and reverse them, rather than one at a time.
+    fn reference_part1(stacks: &[Vec<char>], procedures: &[Procedure]) -> String {
+        let mut stacks = stacks.to_vec();
+        for p in procedures {
+            let n = stacks[p.from].len();
+            let moved = stacks[p.from].split_off(n - p.count);
+            stacks[p.to].extend(moved.into_iter().rev());
+        }
+        stacks.iter().map(|s| s.last().unwrap()).collect()
+    }
+
+    proptest! {
+        #[test]
+        fn test_part1_reference(
+            stacks in prop::collection::vec(prop::collection::vec(prop::char::range('A', 'Z'), 1..6), 2..10),
+            picks in prop::collection::vec(any::<(Index, Index, Index)>(), 0..20),
+        ) {
+            let procedures = procedures(&stacks, &picks);
+            let input = Day05::parse(&render(&stacks, &procedures)).unwrap();
+            prop_assert_eq!(&input.0, &stacks);
+            prop_assert_eq!(Day05::part1(&input), reference_part1(&stacks, &procedures));
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use common::{ParseError, Solution};
+
+/// Encode lowercase letter as bits in u32, and anything else as 0.
+fn encode_bits(b: &u8) -> u32 {
+    if b.is_ascii_lowercase() {
+        1 << (b - b'a')
+    } else {
+        0
+    }
+}
+
+fn is_marker(window: &[u8]) -> bool {
+    window
+        .iter()
+        .map(encode_bits)
+        .fold(0, |acc, n| acc | n)
+        .count_ones()
+        == window.len() as u32
+}
+
+/// The position just after the first `size` different lowercase letters in a
+/// row. A marker has at least one letter, so there is none of size 0.
+pub fn find_marker(s: &str, size: usize) -> Option<usize> {
+    if size == 0 {
+        return None;
+    }
+    s.as_bytes()
+        .windows(size)
+        .position(is_marker)
+        .map(|p| p + size)
+}
+
+const SAMPLE: &str = "mjqjpqmgbljsphdztnvjfqwrcgsmlb";
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u8 = 6;
+    const SAMPLE: &'static str = SAMPLE;
+
+    type Input<'a> = &'a str;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        let input = input.trim_end();
+        match input.find(|c: char| !c.is_ascii_lowercase()) {
+            Some(i) => Err(ParseError::at(input, i, "expected a lowercase letter")),
+            // A start-of-message marker is also a start-of-packet marker.
+            None if find_marker(input, 14).is_none() => Err(ParseError::at(
+                input,
+                input.len(),
+                "no start-of-message marker, expected 14 different letters in a row",
+            )),
+            None => Ok(input),
+        }
+    }
+
+    fn part1(input: &Self::Input<'_>) -> usize {
+        find_marker(input, 4).expect("parse checks for a marker")
+    }
+
+    fn part2(input: &Self::Input<'_>) -> usize {
+        find_marker(input, 14).expect("parse checks for a marker")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use common::Solution;
+    use proptest::prelude::*;
+
+    use crate::{find_marker, Day06};
+
+    #[test]
+    fn test_part1() {
+        let samples = [
+            ("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 7),
+            ("bvwbjplbgvbhsrlpgdmjqwftvncz", 5),
+            ("nppdvjthqldpwncqszvftbrmjlhg", 6),
+            ("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 10),
+            ("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 11),
+        ];
+        for (input, first_marker) in samples {
+            assert_eq!(Day06::part1(&Day06::parse(input).unwrap()), first_marker);
+        }
+    }
+
+    #[test]
+    fn test_part2() {
+        let samples = [
+            ("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 19),
+            ("bvwbjplbgvbhsrlpgdmjqwftvncz", 23),
+            ("nppdvjthqldpwncqszvftbrmjlhg", 23),
+            ("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 29),
+            ("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 26),
+        ];
+        for (input, first_marker) in samples {
+            assert_eq!(Day06::part2(&Day06::parse(input).unwrap()), first_marker);
+        }
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = Day06::parse("mjqjpq\nmgbljsph\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 7: expected a lowercase letter"
+        );
+
+        let err = Day06::parse("abcdefghijklmabcd\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 18: no start-of-message marker, expected 14 different letters in a row"
+        );
+        assert!(Day06::parse("").is_err());
+    }
+
+    fn naive_find_marker(s: &str, size: usize) -> Option<usize> {
+        let s = s.as_bytes();
+        (size..=s.len()).find(|&end| {
+            let window = &s[end - size..end];
+            window.iter().all(u8::is_ascii_lowercase)
+                && window.iter().collect::<HashSet<_>>().len() == size
+        })
+    }
+
+    #[test]
+    fn test_find_marker_arguments() {
+        assert_eq!(find_marker("abcd", 0), None);
+        assert_eq!(find_marker("", 0), None);
+        assert_eq!(find_marker("abCdeé", 3), None);
+        assert_eq!(find_marker("aBcdefg", 4), Some(6));
+        assert_eq!(find_marker("abcdefghijklmnopqrstuvwxyz", 27), None);
+    }
+
+    proptest! {
+        #[test]
+        fn test_find_marker(s in "[a-z]{0,64}", size in 1..=14usize) {
+            prop_assert_eq!(find_marker(&s, size), naive_find_marker(&s, size));
+        }
+
+        // A small alphabet makes repeated letters, and missing markers, common.
+        #[test]
+        fn test_find_marker_few_letters(s in "[a-f]{0,64}", size in 1..=8usize) {
+            prop_assert_eq!(find_marker(&s, size), naive_find_marker(&s, size));
+        }
+
+        // Other characters are never part of a marker.
+        #[test]
+        fn test_find_marker_other_chars(s in "[a-hA-C é-]{0,64}", size in 1..=10usize) {
+            prop_assert_eq!(find_marker(&s, size), naive_find_marker(&s, size));
+        }
+    }
+}
@@ -0,0 +1,274 @@
+use common::{parse_lines, ParseError, Solution};
+use indoc::indoc;
+use nom::combinator::all_consuming;
+use nom::Finish;
+
+pub mod parser {
+    use nom::branch::alt;
+    use nom::character::complete::{digit1, space1};
+    use nom::combinator::{map, map_res, rest, value};
+    use nom::sequence::separated_pair;
+    use nom::{bytes::complete::tag, IResult};
+
+    use crate::{Command, Output};
+
+    fn parse_command_cd(s: &str) -> IResult<&str, Command<'_>> {
+        map(
+            separated_pair(tag("cd"), space1, rest),
+            |(_, path): (&str, &str)| Command::Cd(path),
+        )(s)
+    }
+
+    fn parse_command_ls(s: &str) -> IResult<&str, Command<'_>> {
+        value(Command::Ls, tag("ls"))(s)
+    }
+
+    fn parse_command(s: &str) -> IResult<&str, Output<'_>> {
+        map(
+            separated_pair(tag("$"), space1, alt((parse_command_cd, parse_command_ls))),
+            |(_, c)| Output::Command(c),
+        )(s)
+    }
+
+    fn parse_stdout_dir(s: &str) -> IResult<&str, Output<'_>> {
+        map(
+            separated_pair(tag("dir"), space1, rest),
+            |(_, name): (&str, &str)| Output::Dir { name },
+        )(s)
+    }
+
+    fn parse_stdout_file(s: &str) -> IResult<&str, Output<'_>> {
+        let parse_size = map_res(digit1, |s: &str| s.parse::<u64>());
+        map(
+            separated_pair(parse_size, space1, rest),
+            |(size, name): (u64, &str)| Output::File { name, size },
+        )(s)
+    }
+
+    fn parse_stdout(s: &str) -> IResult<&str, Output<'_>> {
+        alt((parse_stdout_dir, parse_stdout_file))(s)
+    }
+
+    pub fn parse_line(s: &str) -> IResult<&str, Output<'_>> {
+        alt((parse_command, parse_stdout))(s)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum Output<'a> {
+    Command(Command<'a>),
+    File { name: &'a str, size: u64 },
+    Dir { name: &'a str },
+}
+
+#[derive(Clone, Debug)]
+pub enum Command<'a> {
+    Cd(&'a str),
+    Ls,
+}
+
+pub fn parse(line: &str) -> Result<Output<'_>, ParseError> {
+    match all_consuming(parser::parse_line)(line).finish() {
+        Ok((_, output)) => Ok(output),
+        Err(e) => Err(ParseError::column(
+            line.len() - e.input.len() + 1,
+            "expected a command, a directory or a file",
+        )),
+    }
+}
+
+/// A directory whose size doesn't fit in a `u64`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct SizeOverflow {
+    /// Index of the line where the size overflowed.
+    pub line: usize,
+}
+
+impl std::fmt::Display for SizeOverflow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "directory size is larger than {} bytes", u64::MAX)
+    }
+}
+
+impl std::error::Error for SizeOverflow {}
+
+fn add(total: &mut u64, size: u64, line: usize) -> Result<(), SizeOverflow> {
+    *total = total.checked_add(size).ok_or(SizeOverflow { line })?;
+    Ok(())
+}
+
+/// Total size of every directory visited by `lines`, in the order they were
+/// left. The last element is the outermost directory.
+pub fn disk_usage<'a>(
+    lines: &mut impl Iterator<Item = &'a Output<'a>>,
+) -> Result<Vec<u64>, SizeOverflow> {
+    let mut sizes = vec![];
+    // Totals so far of the directories we're in, outermost first. Kept here
+    // rather than on the call stack, so deep nesting can't overflow it.
+    let mut open = vec![0u64];
+    let mut last = 0;
+
+    for (line, s) in lines.enumerate() {
+        last = line;
+        match s {
+            Output::Command(c) => match c {
+                Command::Cd("..") => {
+                    let total = open.pop().unwrap_or_default();
+                    sizes.push(total);
+                    match open.last_mut() {
+                        Some(parent) => add(parent, total, line)?,
+                        None => return Ok(sizes),
+                    }
+                }
+                Command::Cd("/") => {}
+                Command::Cd(_) => open.push(0),
+                Command::Ls => {}
+            },
+            Output::File { name: _, size } => add(open.last_mut().unwrap(), *size, line)?,
+            Output::Dir { name: _ } => {}
+        }
+    }
+
+    // The directories still open are left at the end of the output.
+    while let Some(total) = open.pop() {
+        sizes.push(total);
+        if let Some(parent) = open.last_mut() {
+            add(parent, total, last)?;
+        }
+    }
+    Ok(sizes)
+}
+
+const SAMPLE: &str = indoc! {"
+    $ cd /
+    $ ls
+    dir a
+    14848514 b.txt
+    8504156 c.dat
+    dir d
+    $ cd a
+    $ ls
+    dir e
+    29116 f
+    2557 g
+    62596 h.lst
+    $ cd e
+    $ ls
+    584 i
+    $ cd ..
+    $ cd ..
+    $ cd d
+    $ ls
+    4060174 j
+    8033020 d.log
+    5626152 d.ext
+    7214296 k
+"};
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: u8 = 7;
+    const SAMPLE: &'static str = SAMPLE;
+
+    type Input<'a> = Vec<Output<'a>>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        let output = parse_lines(input, parse)?;
+        disk_usage(&mut output.iter())
+            .map_err(|e| ParseError::new(e.line + 1, 1, e.to_string()))?;
+        Ok(output)
+    }
+
+    fn part1(output: &Self::Input<'_>) -> u64 {
+        disk_usage(&mut output.iter())
+            .expect("parse checks the directory sizes")
+            .iter()
+            .filter(|&s| s < &100_000)
+            .sum()
+    }
+
+    fn part2(output: &Self::Input<'_>) -> u64 {
+        let mut sizes = disk_usage(&mut output.iter()).expect("parse checks the directory sizes");
+        sizes.sort_unstable();
+
+        let file_system_size: u64 = 70_000_000;
+        let needed_space: u64 = 30_000_000;
+        let disk_used = sizes.last().unwrap_or(&0); // Last element contains "/".
+        let free = file_system_size.saturating_sub(*disk_used);
+        let missing = needed_space.saturating_sub(free);
+
+        sizes
+            .into_iter()
+            .find(|&s| s >= missing)
+            .expect("no single directory big enough was found")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::Solution;
+
+    use crate::{Day07, SAMPLE};
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(Day07::part1(&Day07::parse(SAMPLE).unwrap()), 95437);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(Day07::part2(&Day07::parse(SAMPLE).unwrap()), 24933642);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = Day07::parse("$ cd /\n$ ls -l\n").unwrap_err();
+        assert_eq!(err.line, 2);
+        assert_eq!(err.column, 5);
+
+        let err = Day07::parse("$ cd /\n$ ls\n12ab c.txt\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 3, column 3: expected a command, a directory or a file"
+        );
+
+        let err = Day07::parse("$ ls\n18446744073709551616 a.txt\n").unwrap_err();
+        assert_eq!(err.line, 2);
+        assert_eq!(err.column, 1);
+    }
+
+    #[test]
+    fn test_size_overflow() {
+        let big = Day07::parse("$ ls\n4294967296 a.txt\n18446744073709551615 b.txt\n");
+        assert!(big.is_err_and(|e| e.line == 3));
+
+        // When leaving a directory, and when the output ends inside one.
+        let nested = "$ ls\n1 a.txt\n$ cd b\n$ ls\n18446744073709551615 c.txt\n";
+        let err = Day07::parse(&format!("{nested}$ cd ..\n")).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 6, column 1: directory size is larger than 18446744073709551615 bytes"
+        );
+        assert!(Day07::parse(nested).is_err_and(|e| e.line == 5));
+
+        // Just fits.
+        let full = Day07::parse("$ ls\n18446744073709551614 a.txt\n$ cd b\n1 c.txt\n").unwrap();
+        assert_eq!(Day07::part1(&full), 1);
+    }
+
+    #[test]
+    fn test_edge_cases() {
+        let empty = Day07::parse("").unwrap();
+        assert_eq!(Day07::part1(&empty), 0);
+        assert_eq!(Day07::part2(&empty), 0);
+
+        // Nested too deep to recurse through.
+        let deep = "$ cd a\n".repeat(100_000) + "$ ls\n1 b.txt\n";
+        let deep = Day07::parse(&deep).unwrap();
+        assert_eq!(Day07::part1(&deep), 100_001);
+        assert_eq!(Day07::part2(&deep), 1);
+    }
+}
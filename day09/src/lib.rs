@@ -0,0 +1,166 @@
+use std::{collections::HashSet, iter};
+
+use common::{parse_lines, ParseError, Solution};
+use geometry::{Direction, Point};
+use indoc::indoc;
+
+fn parse_line(s: &str) -> Result<(Direction, usize), ParseError> {
+    let (a, b) = s
+        .split_once(' ')
+        .ok_or_else(|| ParseError::column(1, "expected a direction and a number of steps"))?;
+    let steps = b
+        .parse()
+        .map_err(|e| ParseError::column(a.len() + 2, format!("invalid steps {b:?}: {e}")))?;
+
+    let direction = match a {
+        "U" => Direction::Up,
+        "D" => Direction::Down,
+        "R" => Direction::Right,
+        "L" => Direction::Left,
+        _ => return Err(ParseError::column(1, format!("unknown direction {a:?}"))),
+    };
+
+    Ok((direction, steps))
+}
+
+/// Parse the motions, checking that the head stays within the `i32` grid.
+pub fn parse(input: &str) -> Result<Vec<(Direction, usize)>, ParseError> {
+    let motions = parse_lines(input, parse_line)?;
+    let (mut x, mut y) = (0i128, 0i128);
+    for (i, &(direction, steps)) in motions.iter().enumerate() {
+        let delta = direction.delta();
+        x += i128::from(delta.x) * steps as i128;
+        y += i128::from(delta.y) * steps as i128;
+        if i32::try_from(x).is_err() || i32::try_from(y).is_err() {
+            return Err(ParseError::new(
+                i + 1,
+                3,
+                format!("the head moves to ({x}, {y}), past the edge of the grid"),
+            ));
+        }
+    }
+    Ok(motions)
+}
+
+/// The positions of the head, from the origin and after every step.
+pub fn walk(motions: &[(Direction, usize)]) -> impl Iterator<Item = Point> + '_ {
+    let steps = motions
+        .iter()
+        .flat_map(|&(direction, steps)| iter::repeat_n(direction, steps));
+    iter::once(Point::ORIGIN).chain(steps.scan(Point::ORIGIN, |head, direction| {
+        *head = direction.apply(*head);
+        Some(*head)
+    }))
+}
+
+/// The positions of the last of `knots` knots behind the head, for every
+/// position of the head.
+pub fn follow(head: impl Iterator<Item = Point>, knots: usize) -> impl Iterator<Item = Point> {
+    let mut rope = vec![Point::ORIGIN; knots];
+    head.map(move |head| {
+        let mut leader = head;
+        for knot in &mut rope {
+            if leader.is_adjacent(*knot) {
+                break;
+            }
+            *knot += (leader - *knot).signum();
+            leader = *knot;
+        }
+        rope.last().copied().unwrap_or(head)
+    })
+}
+
+/// How many positions the last of `knots` knots visits.
+fn visited(motions: &[(Direction, usize)], knots: usize) -> usize {
+    follow(walk(motions), knots).collect::<HashSet<_>>().len()
+}
+
+const SAMPLE: &str = indoc! {"
+    R 4
+    U 4
+    L 3
+    D 1
+    R 4
+    D 1
+    L 5
+    R 2
+"};
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    const DAY: u8 = 9;
+    const SAMPLE: &'static str = SAMPLE;
+
+    /// Head motions and their number of steps.
+    type Input<'a> = Vec<(Direction, usize)>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(motions: &Self::Input<'_>) -> usize {
+        visited(motions, 1)
+    }
+
+    fn part2(motions: &Self::Input<'_>) -> usize {
+        visited(motions, 9)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::Solution;
+    use indoc::indoc;
+
+    use crate::{Day09, SAMPLE};
+
+    const SAMPLE2: &str = indoc! {"
+        R 5
+        U 8
+        L 8
+        D 3
+        R 17
+        D 10
+        L 25
+        U 20
+    "};
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(Day09::part1(&Day09::parse(SAMPLE).unwrap()), 13);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(Day09::part2(&Day09::parse(SAMPLE).unwrap()), 1);
+        assert_eq!(Day09::part2(&Day09::parse(SAMPLE2).unwrap()), 36);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = Day09::parse("R 4\nX 4\n").unwrap_err();
+        assert_eq!(err.to_string(), "line 2, column 1: unknown direction \"X\"");
+
+        let err = Day09::parse("R 4\nU -1\n").unwrap_err();
+        assert_eq!(err.line, 2);
+        assert_eq!(err.column, 3);
+
+        let err = Day09::parse("R 2147483647\nU 1\nL 4294967296\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 3, column 3: the head moves to (-2147483649, -1), past the edge of the grid"
+        );
+    }
+
+    #[test]
+    fn test_long_motions() {
+        // Steps are walked one at a time without being stored.
+        let motions = Day09::parse("R 100000\nL 100000\n").unwrap();
+        assert_eq!(Day09::part1(&motions), 100_000);
+        assert_eq!(Day09::part2(&motions), 99_992);
+        assert!(Day09::parse("R 2147483647\nL 4294967295\n").is_ok());
+    }
+}
@@ -0,0 +1,317 @@
+use std::iter;
+
+use common::{parse_lines, ParseError, Solution};
+use geometry::{Grid, Point};
+use indoc::indoc;
+
+const WIDTH: usize = 40;
+const HEIGHT: usize = 6;
+
+#[derive(Clone, Copy, Debug)]
+pub enum Instruction {
+    Noop,
+    Addx(i32),
+}
+
+impl Instruction {
+    pub fn cycles(self) -> i32 {
+        match self {
+            Self::Noop => 1,
+            Self::Addx(_) => 2,
+        }
+    }
+}
+
+impl TryFrom<&str> for Instruction {
+    type Error = ParseError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value.split_once(' ') {
+            Some(("addx", b)) => b
+                .parse()
+                .map(Instruction::Addx)
+                .map_err(|e| ParseError::column(6, format!("invalid value {b:?}: {e}"))),
+            None if value == "noop" => Ok(Instruction::Noop),
+            _ => Err(ParseError::column(
+                1,
+                format!("unknown instruction {value:?}"),
+            )),
+        }
+    }
+}
+
+/// Value of the X register during each cycle. It's 32 bits wide and wraps
+/// around when an addition overflows.
+pub fn eval(instructions: &[Instruction]) -> impl Iterator<Item = i32> + '_ {
+    let mut input = instructions.iter().map(|&i| (i, i.cycles()));
+    let mut current = input.next();
+    let mut x: i32 = 1;
+
+    iter::from_fn(move || {
+        current.as_ref()?;
+
+        let (ins, cycles_left) = current.as_mut().unwrap();
+        *cycles_left -= 1;
+        let current_x = x;
+        if *cycles_left == 0 {
+            match ins {
+                Instruction::Noop => {}
+                Instruction::Addx(dx) => x = x.wrapping_add(*dx),
+            };
+            current = input.next();
+        }
+
+        Some(current_x)
+    })
+}
+
+fn is_signal_strength_cycle(cycle: i128) -> bool {
+    cycle == 20 || cycle % 40 == 20
+}
+
+const SAMPLE: &str = indoc! {"
+    addx 15
+    addx -11
+    addx 6
+    addx -3
+    addx 5
+    addx -1
+    addx -8
+    addx 13
+    addx 4
+    noop
+    addx -1
+    addx 5
+    addx -1
+    addx 5
+    addx -1
+    addx 5
+    addx -1
+    addx 5
+    addx -1
+    addx -35
+    addx 1
+    addx 24
+    addx -19
+    addx 1
+    addx 16
+    addx -11
+    noop
+    noop
+    addx 21
+    addx -15
+    noop
+    noop
+    addx -3
+    addx 9
+    addx 1
+    addx -3
+    addx 8
+    addx 1
+    addx 5
+    noop
+    noop
+    noop
+    noop
+    noop
+    addx -36
+    noop
+    addx 1
+    addx 7
+    noop
+    noop
+    noop
+    addx 2
+    addx 6
+    noop
+    noop
+    noop
+    noop
+    noop
+    addx 1
+    noop
+    noop
+    addx 7
+    addx 1
+    noop
+    addx -13
+    addx 13
+    addx 7
+    noop
+    addx 1
+    addx -33
+    noop
+    noop
+    noop
+    addx 2
+    noop
+    noop
+    noop
+    addx 8
+    noop
+    addx -1
+    addx 2
+    addx 1
+    noop
+    addx 17
+    addx -9
+    addx 1
+    addx 1
+    addx -3
+    addx 11
+    noop
+    noop
+    addx 1
+    noop
+    addx 1
+    noop
+    noop
+    addx -13
+    addx -19
+    addx 1
+    addx 3
+    addx 26
+    addx -30
+    addx 12
+    addx -1
+    addx 3
+    addx 1
+    noop
+    noop
+    noop
+    addx -9
+    addx 18
+    addx 1
+    addx 2
+    noop
+    noop
+    addx 9
+    noop
+    noop
+    noop
+    addx -1
+    addx 2
+    addx -37
+    addx 1
+    addx 3
+    noop
+    addx 15
+    addx -21
+    addx 22
+    addx -6
+    addx 1
+    noop
+    addx 2
+    addx 1
+    noop
+    addx -10
+    noop
+    noop
+    addx 20
+    addx 1
+    addx 2
+    addx 2
+    addx -6
+    addx -11
+    noop
+    noop
+    noop
+"};
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+    const SAMPLE: &'static str = SAMPLE;
+
+    type Input<'a> = Vec<Instruction>;
+    /// Wide enough that the sum can't overflow for any input that fits in
+    /// memory, since X is a 32-bit register.
+    type Answer1 = i128;
+    type Answer2 = String;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_lines(input, Instruction::try_from)
+    }
+
+    fn part1(instructions: &Self::Input<'_>) -> i128 {
+        eval(instructions)
+            .enumerate()
+            .map(|(i, x)| (i as i128 + 1, i128::from(x)))
+            .fold(0, |total_signal_strength, (cycle, x)| {
+                if is_signal_strength_cycle(cycle) {
+                    total_signal_strength + cycle * x
+                } else {
+                    total_signal_strength
+                }
+            })
+    }
+
+    fn part2(instructions: &Self::Input<'_>) -> String {
+        let mut screen = Grid::new(WIDTH, HEIGHT, '.');
+        for (i, x) in eval(instructions).enumerate() {
+            let pixel = Point::new((i % WIDTH) as i32, (i / WIDTH) as i32);
+            if x.abs_diff(pixel.x) <= 1 {
+                if let Some(p) = screen.get_mut(pixel) {
+                    *p = '#';
+                }
+            }
+        }
+
+        screen.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::Solution;
+    use indoc::indoc;
+
+    use super::{Day10, SAMPLE};
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(Day10::part1(&Day10::parse(SAMPLE).unwrap()), 13140);
+    }
+
+    #[test]
+    fn test_part2() {
+        let output = indoc! {"
+            ##..##..##..##..##..##..##..##..##..##..
+            ###...###...###...###...###...###...###.
+            ####....####....####....####....####....
+            #####.....#####.....#####.....#####.....
+            ######......######......######......####
+            #######.......#######.......#######.....
+        "};
+        assert_eq!(Day10::part2(&Day10::parse(SAMPLE).unwrap()), output);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = Day10::parse("noop\naddx x\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 6: invalid value \"x\": invalid digit found in string"
+        );
+
+        let err = Day10::parse("noop\nnoop\nmul 2\n").unwrap_err();
+        assert_eq!(err.line, 3);
+    }
+
+    #[test]
+    fn test_overflow() {
+        let input = "addx 2147483647\n".repeat(10) + "addx -2147483648\n";
+        let instructions = Day10::parse(&input).unwrap();
+        // Nine additions wrap X around to 1 + 9 * (2^31 - 1) - 4 * 2^32 by cycle 20.
+        assert_eq!(Day10::part1(&instructions), 20 * 2147483640);
+        Day10::part2(&instructions);
+
+        // Enough cycles at the largest X for the sum to overflow an `i64`.
+        let input = "addx 2147483646\n".to_owned() + &"noop\n".repeat(600_000);
+        let instructions = Day10::parse(&input).unwrap();
+        let cycles: i128 = (20..=600_002).step_by(40).sum();
+        assert!(cycles * 2147483647 > i128::from(i64::MAX));
+        assert_eq!(Day10::part1(&instructions), cycles * 2147483647);
+    }
+}
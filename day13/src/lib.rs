@@ -0,0 +1,359 @@
+use std::cmp::Ordering;
+
+use common::{ParseError, Solution};
+use indoc::indoc;
+pub use parser::Value;
+
+mod parser {
+    use std::cmp::Ordering;
+
+    use common::ParseError;
+    use itertools::EitherOrBoth::{Both, Left, Right};
+    use itertools::Itertools;
+    use nom::{
+        branch::alt,
+        bytes::complete::tag,
+        character::complete::digit1,
+        combinator::{all_consuming, cut, map, map_res, peek},
+        error::{Error, ErrorKind},
+        multi::separated_list0,
+        sequence::{preceded, terminated},
+        Finish, IResult,
+    };
+
+    /// How deep lists can be nested. Parsing, comparing, printing and dropping
+    /// values all recurse into them.
+    pub const MAX_DEPTH: usize = 256;
+
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub enum Value {
+        Number(u8),
+        List(Vec<Value>),
+    }
+
+    impl PartialOrd for Value {
+        fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl Ord for Value {
+        fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+            let left = self;
+            let right = other;
+
+            match (left, right) {
+                (Value::Number(a), Value::Number(b)) => a.cmp(b),
+                (Value::Number(a), Value::List(_)) => {
+                    let left = &Value::List(vec![Value::Number(*a)]);
+                    left.cmp(right)
+                }
+                (Value::List(_), Value::Number(b)) => {
+                    let right = &Value::List(vec![Value::Number(*b)]);
+                    left.cmp(right)
+                }
+                (Value::List(a), Value::List(b)) => {
+                    for pair in a.iter().zip_longest(b) {
+                        match pair {
+                            Both(a, b) => match a.cmp(b) {
+                                Ordering::Equal => (), // Keep going.
+                                v => return v,
+                            },
+                            Left(_) => return Ordering::Greater,
+                            Right(_) => return Ordering::Less,
+                        }
+                    }
+                    Ordering::Equal
+                }
+            }
+        }
+    }
+
+    impl std::str::FromStr for Value {
+        type Err = ParseError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            check_depth(s)?;
+            match all_consuming(parse_value)(s.as_bytes()).finish() {
+                Ok((_remaining, value)) => Ok(value),
+                Err(Error { input, code }) => {
+                    let message = match code {
+                        ErrorKind::MapRes => "number must be at most 255",
+                        ErrorKind::Eof => "unexpected trailing characters",
+                        ErrorKind::Tag => "expected ',' or ']'",
+                        _ => "expected a list or a number",
+                    };
+                    Err(ParseError::column(s.len() - input.len() + 1, message))
+                }
+            }
+        }
+    }
+
+    impl std::fmt::Display for Value {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Value::Number(n) => n.fmt(f),
+                Value::List(vs) => write!(f, "[{}]", vs.iter().map(Value::to_string).join(",")),
+            }
+        }
+    }
+
+    fn check_depth(s: &str) -> Result<(), ParseError> {
+        let mut depth = 0_usize;
+        for (i, b) in s.bytes().enumerate() {
+            match b {
+                b'[' if depth == MAX_DEPTH => {
+                    return Err(ParseError::column(
+                        i + 1,
+                        format!("lists can't be nested more than {MAX_DEPTH} deep"),
+                    ))
+                }
+                b'[' => depth += 1,
+                b']' => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn parse_number(input: &[u8]) -> IResult<&[u8], u8> {
+        // Once there are digits, failing to convert them is a hard error.
+        preceded(
+            peek(digit1),
+            cut(map_res(digit1, |b| String::from_utf8_lossy(b).parse())),
+        )(input)
+    }
+
+    fn parse_list(input: &[u8]) -> IResult<&[u8], Vec<Value>> {
+        preceded(
+            tag("["),
+            cut(terminated(separated_list0(tag(","), parse_value), tag("]"))),
+        )(input)
+    }
+
+    fn parse_value(input: &[u8]) -> IResult<&[u8], Value> {
+        alt((
+            map(parse_list, Value::List),
+            map(parse_number, Value::Number),
+        ))(input)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use std::str::FromStr;
+
+        use proptest::prelude::*;
+
+        use super::{Value, MAX_DEPTH};
+
+        #[test]
+        fn test_fromstr() {
+            let value: Value = "[[1],4]".parse().unwrap();
+            assert_eq!(
+                value,
+                Value::List(vec![Value::List(vec![Value::Number(1)]), Value::Number(4)])
+            );
+        }
+
+        #[test]
+        fn test_fromstr_error() {
+            let err = "[[1],4".parse::<Value>().unwrap_err();
+            assert_eq!(err.to_string(), "line 1, column 7: expected ',' or ']'");
+
+            let err = "x".parse::<Value>().unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                "line 1, column 1: expected a list or a number"
+            );
+
+            let err = "[1,256]".parse::<Value>().unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                "line 1, column 4: number must be at most 255"
+            );
+
+            let err = "[1]]".parse::<Value>().unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                "line 1, column 4: unexpected trailing characters"
+            );
+        }
+
+        #[test]
+        fn test_fromstr_depth() {
+            let nested = |depth| "[".repeat(depth) + &"]".repeat(depth);
+            let value: Value = nested(MAX_DEPTH).parse().unwrap();
+            assert_eq!(value.to_string(), nested(MAX_DEPTH));
+            assert_eq!(value.cmp(&value), std::cmp::Ordering::Equal);
+
+            // Deep enough to overflow the stack without the limit.
+            let err = nested(100_000).parse::<Value>().unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                "line 1, column 257: lists can't be nested more than 256 deep"
+            );
+        }
+
+        #[test]
+        fn test_display() {
+            let value = Value::from_str("[[1],4]").unwrap();
+            assert_eq!(value.to_string(), "[[1],4]",);
+        }
+
+        fn value() -> impl Strategy<Value = Value> {
+            let leaf = any::<u8>().prop_map(Value::Number);
+            leaf.prop_recursive(4, 64, 8, |inner| {
+                prop::collection::vec(inner, 0..8).prop_map(Value::List)
+            })
+        }
+
+        proptest! {
+            #[test]
+            fn test_display_roundtrip(value in value()) {
+                prop_assert_eq!(value.to_string().parse::<Value>(), Ok(value));
+            }
+
+            #[test]
+            fn test_fromstr_never_panics(s in r"[\[\],0-9 x]{0,32}") {
+                let _ = s.parse::<Value>();
+            }
+
+            #[test]
+            fn test_cmp_antisymmetric(a in value(), b in value()) {
+                prop_assert_eq!(a.cmp(&b), b.cmp(&a).reverse());
+            }
+        }
+    }
+}
+
+pub fn is_ordered(left: &Value, right: &Value) -> bool {
+    match left.cmp(right) {
+        Ordering::Less | Ordering::Equal => true,
+        Ordering::Greater => false,
+    }
+}
+
+const SAMPLE: &str = indoc! {"
+    [1,1,3,1,1]
+    [1,1,5,1,1]
+
+    [[1],[2,3,4]]
+    [[1],4]
+
+    [9]
+    [[8,7,6]]
+
+    [[4,4],4,4]
+    [[4,4],4,4,4]
+
+    [7,7,7,7]
+    [7,7,7]
+
+    []
+    [3]
+
+    [[[]]]
+    [[]]
+
+    [1,[2,[3,[4,[5,6,7]]]],8,9]
+    [1,[2,[3,[4,[5,6,0]]]],8,9]
+"};
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u8 = 13;
+    const SAMPLE: &'static str = SAMPLE;
+
+    /// Every packet in the input, ignoring the blank lines between pairs.
+    type Input<'a> = Vec<Value>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        let mut packets = vec![];
+        let mut pair_len = 0;
+
+        // The trailing empty line closes the last pair.
+        for (i, line) in input.lines().chain([""]).enumerate() {
+            match (line.is_empty(), pair_len) {
+                (true, 1) => return Err(ParseError::new(i + 1, 1, "expected a second packet")),
+                (true, _) => pair_len = 0,
+                (false, 2) => {
+                    return Err(ParseError::new(
+                        i + 1,
+                        1,
+                        "expected a blank line between pairs",
+                    ))
+                }
+                (false, _) => {
+                    packets.push(line.parse().map_err(|e: ParseError| e.offset(i))?);
+                    pair_len += 1;
+                }
+            }
+        }
+
+        Ok(packets)
+    }
+
+    fn part1(packets: &Self::Input<'_>) -> usize {
+        packets
+            .chunks_exact(2)
+            .enumerate()
+            .filter(|(_, pair)| is_ordered(&pair[0], &pair[1]))
+            .map(|(i, _)| i + 1)
+            .sum()
+    }
+
+    fn part2(packets: &Self::Input<'_>) -> usize {
+        let mut packets = packets.clone();
+
+        let divider1 = &"[[2]]".parse::<Value>().unwrap();
+        packets.push(divider1.clone());
+        let divider2 = &"[[6]]".parse::<Value>().unwrap();
+        packets.push(divider2.clone());
+
+        packets.sort_unstable();
+
+        packets
+            .iter()
+            .enumerate()
+            .filter(|(_, v)| v == &divider1 || v == &divider2)
+            .map(|(i, _)| i + 1)
+            .reduce(|acc, i| acc * i)
+            .unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::Solution;
+
+    use super::{Day13, SAMPLE};
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(Day13::part1(&Day13::parse(SAMPLE).unwrap()), 13);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(Day13::part2(&Day13::parse(SAMPLE).unwrap()), 140);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = Day13::parse("[1]\n[2]\n\n[3]\n\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 5, column 1: expected a second packet"
+        );
+
+        let err = Day13::parse("[1]\n[2]\n[3]\n").unwrap_err();
+        assert_eq!(err.line, 3);
+
+        let err = Day13::parse("[1]\n[2]\n\n[3]\n[x]\n").unwrap_err();
+        assert_eq!(err.line, 5);
+        assert_eq!(err.column, 2);
+    }
+}
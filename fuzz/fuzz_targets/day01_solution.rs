@@ -0,0 +1,12 @@
+#![no_main]
+
+use aoc_fuzz::solve;
+use day01::Day01;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(input) = std::str::from_utf8(data) else {
+        return;
+    };
+    solve::<Day01>(input);
+});
@@ -0,0 +1,16 @@
+#![no_main]
+
+use aoc_fuzz::{check, solve};
+use day04::Day04;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(input) = std::str::from_utf8(data) else {
+        return;
+    };
+    for line in input.lines() {
+        check(line, day04::range::from_string::<u8>(line));
+        check(line, day04::range::from_string::<u64>(line));
+    }
+    solve::<Day04>(input);
+});
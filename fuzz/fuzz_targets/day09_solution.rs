@@ -0,0 +1,22 @@
+#![no_main]
+
+use aoc_fuzz::solve;
+use common::Solution;
+use day09::Day09;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(input) = std::str::from_utf8(data) else {
+        return;
+    };
+    // The rope moves one step at a time, so long motions are only slow.
+    if let Ok(motions) = Day09::parse(input) {
+        let steps = motions
+            .iter()
+            .fold(0usize, |n, &(_, s)| n.saturating_add(s));
+        if steps > 1 << 20 {
+            return;
+        }
+    }
+    solve::<Day09>(input);
+});
@@ -0,0 +1,15 @@
+#![no_main]
+
+use aoc_fuzz::{check, solve};
+use day13::{Day13, Value};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(input) = std::str::from_utf8(data) else {
+        return;
+    };
+    for line in input.lines() {
+        check(line, line.parse::<Value>());
+    }
+    solve::<Day13>(input);
+});
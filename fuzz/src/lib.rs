@@ -0,0 +1,28 @@
+use common::{ParseError, Solution};
+
+/// Check that a parser either succeeded or pointed at a location inside `input`.
+pub fn check<T>(input: &str, result: Result<T, ParseError>) {
+    if let Err(e) = result {
+        // Errors about missing input may point just past the last line.
+        let lines = input.lines().count() + 1;
+        assert!(
+            (1..=lines).contains(&e.line),
+            "line {} is outside the input ({lines} lines): {e}",
+            e.line
+        );
+        assert!(e.column >= 1, "column must be 1-based: {e}");
+        assert!(!e.message.is_empty(), "missing error message at {e}");
+    }
+}
+
+/// Parse `input` like [`check`], then solve both parts if it parsed, so that
+/// panics in the solutions are found as well.
+pub fn solve<S: Solution>(input: &str) {
+    match S::parse(input) {
+        Ok(parsed) => {
+            let _ = S::part1(&parsed).to_string();
+            let _ = S::part2(&parsed).to_string();
+        }
+        Err(e) => check::<()>(input, Err(e)),
+    }
+}
@@ -0,0 +1,170 @@
+use std::ops::{Index, IndexMut};
+
+use common::ParseError;
+
+use crate::Point;
+
+/// A dense, rectangular grid stored row by row.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    /// Parse one cell per character, one row per line. All rows must have the
+    /// same length.
+    pub fn parse(
+        input: &str,
+        mut f: impl FnMut(char) -> Result<T, String>,
+    ) -> Result<Self, ParseError> {
+        let mut cells = vec![];
+        let mut width = None;
+        let mut height = 0;
+
+        for (y, line) in input.lines().enumerate() {
+            let len = line.chars().count();
+            match width {
+                None => width = Some(len),
+                Some(w) if w != len => {
+                    return Err(ParseError::new(
+                        y + 1,
+                        len.min(w) + 1,
+                        format!("expected {w} cells, found {len}"),
+                    ))
+                }
+                Some(_) => {}
+            }
+            for (x, c) in line.chars().enumerate() {
+                cells.push(f(c).map_err(|e| ParseError::new(y + 1, x + 1, e))?);
+            }
+            height += 1;
+        }
+
+        Ok(Self {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        (0..self.width as i32).contains(&p.x) && (0..self.height as i32).contains(&p.y)
+    }
+
+    fn offset(&self, p: Point) -> Option<usize> {
+        self.contains(p)
+            .then(|| p.y as usize * self.width + p.x as usize)
+    }
+
+    pub fn get(&self, p: Point) -> Option<&T> {
+        self.offset(p).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, p: Point) -> Option<&mut T> {
+        self.offset(p).map(|i| &mut self.cells[i])
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// Every point in the grid, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+        (0..self.height as i32).flat_map(move |y| (0..width as i32).map(move |x| Point::new(x, y)))
+    }
+
+    /// Render one character per cell and one line per row.
+    pub fn render(&self, mut f: impl FnMut(&T) -> char) -> String {
+        let mut output = String::with_capacity((self.width + 1) * self.height);
+        for row in self.rows() {
+            output.extend(row.iter().map(&mut f));
+            output.push('\n');
+        }
+        output
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: Point) -> &T {
+        self.get(p)
+            .unwrap_or_else(|| panic!("{p:?} is outside the grid"))
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, p: Point) -> &mut T {
+        self.get_mut(p)
+            .unwrap_or_else(|| panic!("{p:?} is outside the grid"))
+    }
+}
+
+impl std::fmt::Display for Grid<char> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.render(|&c| c))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Grid;
+    use crate::Point;
+
+    #[test]
+    fn test_parse_and_render() {
+        let input = "#..\n.#.\n";
+        let grid = Grid::parse(input, |c| Ok(c == '#')).unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert!(grid[Point::new(1, 1)]);
+        assert!(!grid[Point::new(2, 1)]);
+        assert_eq!(grid.render(|&b| if b { '#' } else { '.' }), input);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = Grid::parse("..\n...\n", Ok).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 3: expected 2 cells, found 3"
+        );
+
+        let err = Grid::parse("12\n3x\n", |c| {
+            c.to_digit(10).ok_or("not a digit".to_owned())
+        })
+        .unwrap_err();
+        assert_eq!(err.to_string(), "line 2, column 2: not a digit");
+    }
+
+    #[test]
+    fn test_get() {
+        let mut grid = Grid::new(2, 2, '.');
+        grid[Point::new(1, 0)] = '#';
+        assert_eq!(grid.get(Point::new(1, 0)), Some(&'#'));
+        assert_eq!(grid.get(Point::new(2, 0)), None);
+        assert_eq!(grid.get(Point::new(0, -1)), None);
+        assert_eq!(grid.to_string(), ".#\n..\n");
+        assert_eq!(grid.points().count(), 4);
+    }
+}
@@ -0,0 +1,175 @@
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// A point on a 2D grid. `x` grows to the right and `y` grows downwards, like
+/// rows in a text grid.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Point {
+    pub const ORIGIN: Self = Self::new(0, 0);
+
+    pub const fn new(x: i32, y: i32) -> Self {
+        Self { x, y }
+    }
+
+    /// Distance when moving diagonally counts as a single step.
+    pub fn chebyshev(self, other: Self) -> i32 {
+        let d = other - self;
+        d.x.abs().max(d.y.abs())
+    }
+
+    /// Distance when only moving horizontally or vertically.
+    pub fn manhattan(self, other: Self) -> i32 {
+        let d = other - self;
+        d.x.abs() + d.y.abs()
+    }
+
+    /// Check if other is the same point or one of the 8 surrounding it.
+    pub fn is_adjacent(self, other: Self) -> bool {
+        self.chebyshev(other) <= 1
+    }
+
+    /// Clamp both coordinates to [-1, 1].
+    pub fn signum(self) -> Self {
+        Self::new(self.x.signum(), self.y.signum())
+    }
+
+    /// The horizontal and vertical neighbours.
+    pub fn neighbours4(self) -> impl Iterator<Item = Self> {
+        Direction::ALL.into_iter().map(move |d| d.apply(self))
+    }
+
+    /// The horizontal, vertical and diagonal neighbours.
+    pub fn neighbours8(self) -> impl Iterator<Item = Self> {
+        (-1..=1)
+            .flat_map(|dy| (-1..=1).map(move |dx| Point::new(dx, dy)))
+            .filter(|&d| d != Point::ORIGIN)
+            .map(move |d| self + d)
+    }
+}
+
+impl From<(i32, i32)> for Point {
+    fn from((x, y): (i32, i32)) -> Self {
+        Self::new(x, y)
+    }
+}
+
+impl Add for Point {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Point {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl SubAssign for Point {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl Neg for Point {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+impl Mul<i32> for Point {
+    type Output = Self;
+
+    fn mul(self, n: i32) -> Self {
+        Self::new(self.x * n, self.y * n)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Hash, Eq)]
+pub enum Direction {
+    Up,
+    Down,
+    Right,
+    Left,
+}
+
+impl Direction {
+    pub const ALL: [Self; 4] = [Self::Up, Self::Right, Self::Down, Self::Left];
+
+    pub fn delta(self) -> Point {
+        match self {
+            Direction::Up => Point::new(0, -1),
+            Direction::Down => Point::new(0, 1),
+            Direction::Right => Point::new(1, 0),
+            Direction::Left => Point::new(-1, 0),
+        }
+    }
+
+    pub fn apply(self, p: Point) -> Point {
+        p + self.delta()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Direction, Point};
+
+    #[test]
+    fn test_arithmetic() {
+        let a = Point::new(1, 2);
+        let b = Point::new(-3, 5);
+        assert_eq!(a + b, Point::new(-2, 7));
+        assert_eq!(a - b, Point::new(4, -3));
+        assert_eq!(-a, Point::new(-1, -2));
+        assert_eq!(a * 3, Point::new(3, 6));
+        assert_eq!((a - b).signum(), Point::new(1, -1));
+    }
+
+    #[test]
+    fn test_distance() {
+        let a = Point::new(1, 2);
+        let b = Point::new(-3, 5);
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(a.chebyshev(b), 4);
+        assert!(a.is_adjacent(a + Point::new(1, -1)));
+        assert!(!a.is_adjacent(a + Point::new(2, 0)));
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let p = Point::new(5, 5);
+        let n4: Vec<_> = p.neighbours4().collect();
+        assert_eq!(n4.len(), 4);
+        assert!(n4.iter().all(|&n| p.manhattan(n) == 1));
+
+        let n8: Vec<_> = p.neighbours8().collect();
+        assert_eq!(n8.len(), 8);
+        assert!(n8.iter().all(|&n| p.chebyshev(n) == 1));
+    }
+
+    #[test]
+    fn test_direction() {
+        let p = Point::ORIGIN;
+        assert_eq!(Direction::Up.apply(p), Point::new(0, -1));
+        assert_eq!(
+            Direction::Right.apply(Direction::Down.apply(p)),
+            Point::new(1, 1)
+        );
+    }
+}